@@ -0,0 +1,35 @@
+//! Compare the run time of `decode` and `decode_fast` for a valid and a corrupt frame, run with
+//! `cargo bench`
+
+extern crate dcf77;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use dcf77::{DCF77Error, DCF77Time, DateTime};
+
+/// 2024-03-15 (Friday) 13:37 CET
+const FRAME: u64 = 0x0490_755a_76f4_0000;
+
+const ITERATIONS: u32 = 1_000_000;
+
+/// Print the average run time of one call of `decode` in nanoseconds
+fn measure(name: &str, frame: DCF77Time, decode: fn(&DCF77Time) -> Result<DateTime, DCF77Error>) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let _ = black_box(decode(black_box(&frame)));
+    }
+
+    let nanos = start.elapsed().as_nanos() / ITERATIONS as u128;
+    println!("{:<24} {:>6} ns/iter", name, nanos);
+}
+
+fn main() {
+    let valid = DCF77Time::new(FRAME);
+    let corrupt = DCF77Time::new(FRAME ^ (1 << 58));
+
+    measure("decode valid", valid, DCF77Time::decode);
+    measure("decode_fast valid", valid, DCF77Time::decode_fast);
+    measure("decode corrupt", corrupt, DCF77Time::decode);
+    measure("decode_fast corrupt", corrupt, DCF77Time::decode_fast);
+}
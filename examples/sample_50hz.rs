@@ -0,0 +1,36 @@
+//! Decode a synthetic DCF77 signal sampled at 50 Hz, i.e. every 20ms
+
+extern crate dcf77;
+
+use dcf77::{DCF77Time, SimpleDCF77Decoder};
+
+/// 2024-03-15 (Friday) 13:37 CET
+const FRAME: u64 = 0x0490_755a_76f4_0000;
+
+fn main() {
+    let mut decoder = SimpleDCF77Decoder::with_sample_period_ms(20);
+
+    // Each second starts with a 100ms (0 bit) or 200ms (1 bit) high pulse, followed by a
+    // second without any pulse to mark the start of the next minute
+    for second in 0..120 {
+        let second = second % 60;
+        let pulse_ms = if second < 59 {
+            if (FRAME >> second) & 1 != 0 {
+                200
+            } else {
+                100
+            }
+        } else {
+            0
+        };
+
+        for sample in 0..50 {
+            decoder.read_bit(sample * 20 < pulse_ms);
+
+            if decoder.end_of_cycle() && decoder.cycle_length() == 59 {
+                let time = DCF77Time::new(decoder.raw_data());
+                println!("Decoded {:?}", time.decode());
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use {BitThresholds, Polarity, SimpleDCF77Decoder};
+
+/// A builder for a `SimpleDCF77Decoder` with a non-default configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimpleDCF77DecoderBuilder {
+    periodms: u16,
+    polarity: Polarity,
+    debounce: u8,
+    thresholds: Option<BitThresholds>,
+}
+
+/// The builder starts with the configuration of `SimpleDCF77Decoder::new`: a sample every 10ms,
+/// `Polarity::ActiveHigh`, no debounce filter and the default bit thresholds of the sample period
+impl SimpleDCF77DecoderBuilder {
+    /// Create a new builder with the default configuration
+    pub const fn new() -> Self {
+        Self {
+            periodms: 10,
+            polarity: Polarity::ActiveHigh,
+            debounce: 1,
+            thresholds: None,
+        }
+    }
+
+    /// Set the sample period in milliseconds, see `SimpleDCF77Decoder::with_sample_period_ms`
+    pub fn sample_period_ms(mut self, period_ms: u16) -> Self {
+        self.periodms = period_ms;
+        self
+    }
+
+    /// Set the output polarity of the receiver, see `SimpleDCF77Decoder::set_polarity`
+    pub fn polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Set the number of consecutive samples required for a level change, see
+    /// `SimpleDCF77Decoder::set_debounce`
+    pub fn debounce(mut self, samples: u8) -> Self {
+        self.debounce = samples;
+        self
+    }
+
+    /// Set the minimum numbers of high samples to recognize a bit instead of the default ones
+    /// derived from the sample period, see `SimpleDCF77Decoder::set_bit_thresholds`
+    pub fn thresholds(mut self, thresholds: BitThresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+
+    /// Create the decoder state machine with the configuration
+    pub fn build(self) -> SimpleDCF77Decoder {
+        let mut decoder = SimpleDCF77Decoder::with_sample_period_ms(self.periodms);
+        decoder.set_polarity(self.polarity);
+        decoder.set_debounce(self.debounce);
+        if let Some(thresholds) = self.thresholds {
+            decoder.set_bit_thresholds(thresholds);
+        }
+
+        decoder
+    }
+}
+
+impl Default for SimpleDCF77DecoderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,534 @@
+use {Alignment, DCF77Time, DateTime, SimpleDCF77Decoder};
+
+/// A high level clock which feeds a `SimpleDCF77Decoder` and keeps the latest frame that passed
+/// validation
+pub struct DCF77Clock {
+    decoder: SimpleDCF77Decoder,
+    frame: Option<DCF77Time>,
+    accepted: bool,
+    minconfidence: u8,
+    time: Option<DateTime>,
+    freerunning: bool,
+    maxcorrection: u16,
+    rejected: Option<DateTime>,
+    callbit: Option<bool>,
+    switchover: bool,
+    alignment: Alignment,
+    yearrollover: bool,
+    lenientweekday: bool,
+    second: u8,
+    secondticks: u16,
+    newsecond: bool,
+    goodcycles: u16,
+    cyclefault: bool,
+    synced: bool,
+}
+
+impl Default for DCF77Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The DCF77Clock wraps the sample based decoder and checks every completed cycle before taking
+/// it over. A frame is only accepted if the start bit, all parities and value ranges are correct
+/// and the aggregate confidence of its bits reaches the configured minimum, since a parity check
+/// can also be satisfied by coincidental bit errors.
+///
+/// In free-running mode the clock keeps advancing its time by itself after the first
+/// synchronisation and only applies frames which are within a few minutes of its own estimate, so
+/// a single corrupt frame can't make the clock jump hours away. Two consecutive frames which are
+/// consistent with each other are taken over regardless, to recover from a bad estimate.
+///
+/// When the transmitter switches between its main and backup antenna, signalled by the call bit
+/// (bit 15), the signal can glitch during the switchover minute. The minimum confidence is
+/// therefore waived for exactly the frame in which the call bit toggles, the frame still has to
+/// pass all other checks.
+///
+/// The transmitted weekday is also checked against the calendar date unless this is relaxed with
+/// `set_lenient_weekday`
+impl DCF77Clock {
+    /// Create a new clock without any accepted frame and no minimum confidence, expecting a
+    /// sample every 10ms
+    pub fn new() -> Self {
+        Self::with_sample_period_ms(10)
+    }
+
+    /// Create a new clock expecting a sample every `period_ms` milliseconds, see
+    /// `SimpleDCF77Decoder::with_sample_period_ms`
+    pub fn with_sample_period_ms(period_ms: u16) -> Self {
+        Self {
+            decoder: SimpleDCF77Decoder::with_sample_period_ms(period_ms),
+            frame: None,
+            accepted: false,
+            minconfidence: 0,
+            time: None,
+            freerunning: false,
+            maxcorrection: 2,
+            rejected: None,
+            callbit: None,
+            switchover: false,
+            alignment: Alignment::Nominal,
+            yearrollover: false,
+            lenientweekday: false,
+            second: 0,
+            secondticks: 0,
+            newsecond: false,
+            goodcycles: 0,
+            cyclefault: false,
+            synced: false,
+        }
+    }
+
+    /// Create a new clock in free-running mode starting at the given time, e.g. restored from a
+    /// battery backed RTC after a reboot. The clock is marked as not synchronised until a frame
+    /// confirms the time
+    pub fn from_rtc(seed: DateTime) -> Self {
+        Self::from_rtc_with_sample_period_ms(seed, 10)
+    }
+
+    /// Create a new clock in free-running mode starting at the given time like `from_rtc`,
+    /// expecting a sample every `period_ms` milliseconds
+    pub fn from_rtc_with_sample_period_ms(seed: DateTime, period_ms: u16) -> Self {
+        let mut clock = Self::with_sample_period_ms(period_ms);
+        clock.time = Some(seed);
+        clock.freerunning = true;
+        clock
+    }
+
+    /// Returns true if the time was confirmed by an accepted frame at least once, i.e. isn't only
+    /// based on the seed passed to `from_rtc`
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Set the minimum aggregate bit confidence in percent a frame needs to be accepted
+    pub fn set_min_confidence(&mut self, percent: u8) {
+        self.minconfidence = if percent > 100 { 100 } else { percent };
+    }
+
+    /// Return the configured minimum aggregate bit confidence in percent
+    pub fn min_confidence(&self) -> u8 {
+        self.minconfidence
+    }
+
+    /// Enable or disable the free-running mode
+    pub fn set_free_running(&mut self, enabled: bool) {
+        self.freerunning = enabled;
+    }
+
+    /// Set the maximum difference in minutes between a frame and the free-running time for the
+    /// frame to be applied
+    pub fn set_max_correction(&mut self, minutes: u16) {
+        self.maxcorrection = minutes;
+    }
+
+    /// Accept frames whose weekday doesn't match the calendar date as long as all parities are
+    /// correct. By default such frames are rejected: the weekday is the least critical field but a
+    /// mismatch can also be caused by multiple bit errors cancelling out in the date parity, in
+    /// which case the lenient setting accepts a wrong date
+    pub fn set_lenient_weekday(&mut self, lenient: bool) {
+        self.lenientweekday = lenient;
+    }
+
+    /// Return the current time of the clock, if it was synchronised at least once
+    pub fn time(&self) -> Option<DateTime> {
+        self.time
+    }
+
+    /// Return the second of the minute of the current time, if it was synchronised at least once.
+    /// Without free-running mode the second stops at 59 until the next frame is accepted
+    pub fn second(&self) -> Option<u8> {
+        self.time.map(|_| self.second)
+    }
+
+    /// Returns true if the call bit toggled in the latest checked frame, i.e. the transmitter
+    /// switched between its main and backup antenna
+    pub fn antenna_switchover(&self) -> bool {
+        self.switchover
+    }
+
+    /// Returns true if the latest accepted frame is the first one of a new year, i.e. 00:00 on
+    /// January 1st directly following 23:59 on December 31st of the previous year
+    pub fn is_year_rollover(&self) -> bool {
+        self.yearrollover
+    }
+
+    /// Returns the alignment of the bits chosen for the latest completed cycle, see
+    /// `SimpleDCF77Decoder::best_frame`
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Returns true if the latest completed cycle was only decoded after correcting a phase lock
+    /// which was one second off
+    pub fn phase_corrected(&self) -> bool {
+        self.alignment != Alignment::Nominal
+    }
+
+    /// Return a reference to the underlying decoder, e.g. for live display of the received bits
+    pub fn decoder(&self) -> &SimpleDCF77Decoder {
+        &self.decoder
+    }
+
+    /// Return the latest accepted frame, if any
+    pub fn frame(&self) -> Option<&DCF77Time> {
+        self.frame.as_ref()
+    }
+
+    /// Return the number of consecutive cycles whose frame was accepted without any faulty bit,
+    /// e.g. to only signal a solid lock after a few good minutes
+    pub fn consecutive_good_cycles(&self) -> u16 {
+        self.goodcycles
+    }
+
+    /// Returns true if the latest sample completed a cycle whose frame was accepted
+    pub fn frame_accepted(&self) -> bool {
+        self.accepted
+    }
+
+    /// Check a completed frame with the given aggregate bit confidence and take it over if it
+    /// passes all checks. Returns whether the frame was accepted
+    pub fn accept_frame(&mut self, frame: DCF77Time, confidence: u8) -> bool {
+        // The call bit is only taken over from accepted frames, so a corrupt frame can't make the
+        // following one look like a switchover
+        let callbit = frame.abnormal_operation();
+        self.switchover = self.callbit.is_some_and(|previous| previous != callbit);
+
+        if confidence < self.minconfidence && !self.switchover {
+            return false;
+        }
+
+        let decoded = match frame.decode() {
+            Ok(decoded) => decoded,
+            Err(_) => return false,
+        };
+
+        if !self.lenientweekday && frame.validate_weekday_consistency().is_err() {
+            return false;
+        }
+
+        if let (true, Some(time)) = (self.freerunning, self.time) {
+            let difference = (decoded.utc_minutes() - time.utc_minutes()).abs();
+            if difference > self.maxcorrection as i64 {
+                let consistent = match self.rejected {
+                    Some(rejected) => decoded.utc_minutes() - rejected.utc_minutes() == 1,
+                    None => false,
+                };
+
+                if !consistent {
+                    self.rejected = Some(decoded);
+                    return false;
+                }
+            }
+        }
+
+        self.yearrollover = match self.frame.as_ref().map(DCF77Time::decode) {
+            Some(Ok(previous)) => {
+                (previous.year + 1, previous.month, previous.day, previous.hour, previous.minute)
+                    == (decoded.year, 12, 31, 23, 59)
+                    && (decoded.month, decoded.day, decoded.hour, decoded.minute) == (1, 1, 0, 0)
+            }
+            _ => false,
+        };
+
+        self.frame = Some(frame);
+        self.callbit = Some(callbit);
+        self.time = Some(decoded);
+        self.synced = true;
+        self.second = 0;
+        self.secondticks = 0;
+        self.rejected = None;
+
+        true
+    }
+
+    /// Ingest the latest sample of the GPIO input the DCF77 receiver is connected to, see
+    /// `SimpleDCF77Decoder::read_bit`
+    pub fn read_bit(&mut self, bit: bool) {
+        self.decoder.read_bit(bit);
+
+        let length = self.decoder.cycle_length();
+        self.accepted = if self.decoder.cycle_ended() && (58..=60).contains(&length) {
+            let (frame, alignment) = self
+                .decoder
+                .best_frame()
+                .unwrap_or((self.decoder.received_frame(), Alignment::Nominal));
+            self.alignment = alignment;
+            let confidence = self.decoder.frame_confidence();
+            self.accept_frame(frame, confidence)
+        } else {
+            false
+        };
+
+        if self.decoder.bit_faulty() {
+            self.goodcycles = 0;
+            self.cyclefault = true;
+        } else if self.decoder.cycle_ended() {
+            self.goodcycles = if self.accepted && !self.cyclefault {
+                self.goodcycles.saturating_add(1)
+            } else {
+                0
+            };
+            self.cyclefault = false;
+        }
+
+        self.newsecond = if self.accepted {
+            true
+        } else if self.time.is_some() && (self.freerunning || self.second < 59) {
+            self.secondticks += 1;
+            if self.secondticks >= 1000 / self.decoder.sample_period_ms() {
+                self.secondticks = 0;
+                self.second = (self.second + 1) % 60;
+                if self.second == 0 {
+                    self.time = self.time.map(|time| time.add_minutes(1));
+                }
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+    }
+
+    /// Ingest the latest sample like `read_bit` and call `on_second` with the current time and
+    /// second of the minute whenever a new second begins, i.e. when a frame is accepted and every
+    /// second after that, so a display can be refreshed from the same loop feeding the samples
+    pub fn read_bit_with<F: FnMut(&DateTime, u8)>(&mut self, bit: bool, mut on_second: F) {
+        self.read_bit(bit);
+
+        if let (true, Some(time)) = (self.newsecond, self.time.as_ref()) {
+            on_second(time, self.second);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use testutil::*;
+    use {Alignment, DCF77Clock, DCF77Time};
+
+    #[test]
+    fn free_running_ignores_a_wrong_frame_and_keeps_advancing() {
+        let mut clock = DCF77Clock::new();
+        clock.set_free_running(true);
+        assert!(clock.accept_frame(DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false))), 100));
+
+        let wrong = DCF77Time::new(frame(&time(2024, 3, 15, 18, 0, false)));
+        assert!(!clock.accept_frame(wrong, 100));
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 37, false)));
+
+        for _ in 0..6000 {
+            clock.read_bit(false);
+        }
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 38, false)));
+
+        // A second frame consistent with the rejected one is taken over to recover
+        let next = DCF77Time::new(frame(&time(2024, 3, 15, 18, 1, false)));
+        assert!(clock.accept_frame(next, 100));
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 18, 1, false)));
+    }
+
+    #[test]
+    fn call_bit_toggle_waives_the_min_confidence_once() {
+        let mut clock = DCF77Clock::new();
+        clock.set_min_confidence(80);
+        assert!(clock.accept_frame(DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false))), 100));
+        assert!(!clock.antenna_switchover());
+
+        let switched = frame(&time(2024, 3, 15, 13, 38, false)) | (1 << 15);
+        assert!(clock.accept_frame(DCF77Time::new(switched), 10));
+        assert!(clock.antenna_switchover());
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 38, false)));
+
+        let unchanged = frame(&time(2024, 3, 15, 13, 39, false)) | (1 << 15);
+        assert!(!clock.accept_frame(DCF77Time::new(unchanged), 10));
+        assert!(!clock.antenna_switchover());
+
+        let back = frame(&time(2024, 3, 15, 13, 40, false));
+        assert!(clock.accept_frame(DCF77Time::new(back), 10));
+        assert!(clock.antenna_switchover());
+    }
+
+    #[test]
+    fn shifted_candidate_wins_after_a_spurious_bit() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        // A spurious pulse received in front of the start bit
+        second(&mut samples, 100, 10);
+        minute(&mut samples, sent, 10);
+
+        let mut clock = DCF77Clock::new();
+        let mut accepted = 0;
+        for sample in samples {
+            clock.read_bit(sample);
+            if clock.frame_accepted() {
+                accepted += 1;
+                assert_eq!(clock.decoder().cycle_length(), 60);
+                assert_eq!(clock.alignment(), Alignment::DroppedFirst);
+            }
+        }
+
+        assert_eq!(accepted, 1);
+        assert!(clock.phase_corrected());
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 37, false)));
+    }
+
+    #[test]
+    fn year_rollover_is_flagged_for_the_first_minute_only() {
+        let mut clock = DCF77Clock::new();
+        for &(year, month, day, hour, minute, rollover) in &[
+            (2024, 12, 31, 23, 58, false),
+            (2024, 12, 31, 23, 59, false),
+            (2025, 1, 1, 0, 0, true),
+            (2025, 1, 1, 0, 1, false),
+        ] {
+            let received = DCF77Time::new(frame(&time(year, month, day, hour, minute, false)));
+            assert!(clock.accept_frame(received, 100));
+            assert_eq!(clock.is_year_rollover(), rollover, "{}-{}", year, minute);
+        }
+
+        // Only the minute directly following the end of the year counts
+        let mut clock = DCF77Clock::new();
+        let mut accept = |year, month, day, hour, minute| {
+            let received = DCF77Time::new(frame(&time(year, month, day, hour, minute, false)));
+            assert!(clock.accept_frame(received, 100));
+            clock.is_year_rollover()
+        };
+        accept(2025, 1, 31, 23, 59);
+        assert!(!accept(2025, 2, 1, 0, 0));
+        accept(2024, 12, 31, 23, 59);
+        assert!(!accept(2025, 1, 1, 0, 1));
+    }
+
+    #[test]
+    fn lenient_weekday_accepts_a_mismatch_strict_rejects() {
+        let mut monday = time(2024, 3, 15, 13, 37, false);
+        monday.weekday = 1;
+        let received = DCF77Time::encode(&monday);
+        assert!(received.decode().is_ok());
+
+        let mut clock = DCF77Clock::new();
+        assert!(!clock.accept_frame(received, 100));
+        assert_eq!(clock.time(), None);
+
+        clock.set_lenient_weekday(true);
+        assert!(clock.accept_frame(received, 100));
+        assert_eq!(clock.time(), Some(monday));
+
+        // A consistent weekday is accepted either way
+        clock.set_lenient_weekday(false);
+        assert!(clock.accept_frame(DCF77Time::new(frame(&time(2024, 3, 15, 13, 38, false))), 100));
+    }
+
+    #[test]
+    fn free_running_minute_advances_with_the_seconds() {
+        for &period_ms in &[10, 7] {
+            let mut clock = DCF77Clock::with_sample_period_ms(period_ms);
+            clock.set_free_running(true);
+            let received = DCF77Time::new(frame(&time(2024, 3, 15, 23, 59, false)));
+            assert!(clock.accept_frame(received, 100));
+
+            let mut calls = Vec::new();
+            for _ in 0..60 * (1000 / period_ms) {
+                clock.read_bit_with(false, |time, second| calls.push((time.minute, second)));
+            }
+
+            assert_eq!(calls.len(), 60, "{}ms", period_ms);
+            assert_eq!(calls[0], (59, 1));
+            assert_eq!(calls[58], (59, 59));
+            assert_eq!(calls[59], (0, 0));
+            assert_eq!(clock.time(), Some(time(2024, 3, 16, 0, 0, false)));
+            assert_eq!(clock.second(), Some(0));
+        }
+    }
+
+    #[test]
+    fn good_cycles_are_reset_by_a_fault() {
+        let minutes = [30, 31, 32, 33, 34].map(|m| frame(&time(2024, 3, 15, 13, m, false)));
+        let mut clock = DCF77Clock::new();
+        let mut feed = |samples: &[bool]| {
+            for &sample in samples {
+                clock.read_bit(sample);
+            }
+            clock.consecutive_good_cycles()
+        };
+
+        assert_eq!(feed(&signal(&minutes[..3])), 3);
+
+        // An ambiguous 30ms pulse resets the count right away
+        let mut samples = Vec::new();
+        bits(&mut samples, minutes[3], 0, 10, 10);
+        second(&mut samples, 30, 10);
+        assert_eq!(feed(&samples), 0);
+
+        let mut samples = Vec::new();
+        bits(&mut samples, minutes[3], 11, 59, 10);
+        second(&mut samples, 0, 10);
+        assert_eq!(feed(&samples), 0);
+
+        let mut samples = Vec::new();
+        minute(&mut samples, minutes[4], 10);
+        assert_eq!(feed(&samples), 1);
+    }
+
+    #[test]
+    fn rtc_seed_is_synced_by_a_matching_frame() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 20);
+        minute(&mut samples, sent, 20);
+
+        let seed = time(2024, 3, 15, 13, 36, false);
+        let mut clock = DCF77Clock::from_rtc_with_sample_period_ms(seed, 20);
+        assert_eq!(clock.decoder().sample_period_ms(), 20);
+        assert!(!clock.is_synced());
+
+        let mut accepted = 0;
+        for sample in samples {
+            clock.read_bit(sample);
+            if clock.frame_accepted() {
+                accepted += 1;
+            }
+        }
+
+        // The frame is within the maximum correction of the free-running seed
+        assert_eq!(accepted, 1);
+        assert!(clock.is_synced());
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 37, false)));
+    }
+
+    #[test]
+    fn rejected_frame_does_not_record_a_call_bit_toggle() {
+        let mut clock = DCF77Clock::new();
+        clock.set_min_confidence(80);
+        let received = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        assert!(clock.accept_frame(received, 100));
+
+        // Noise flipped bit 15 and broke the date parity
+        let corrupt = frame(&time(2024, 3, 15, 13, 38, false)) ^ (1 << 15) ^ (1 << 58);
+        assert!(!clock.accept_frame(DCF77Time::new(corrupt), 100));
+
+        let next = DCF77Time::new(frame(&time(2024, 3, 15, 13, 39, false)));
+        assert!(!clock.accept_frame(next, 10));
+        assert!(!clock.antenna_switchover());
+        assert!(clock.accept_frame(next, 100));
+    }
+
+    #[test]
+    fn frame_below_min_confidence_is_rejected() {
+        let received = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let mut clock = DCF77Clock::new();
+        clock.set_min_confidence(80);
+
+        assert!(received.decode().is_ok());
+        assert!(!clock.accept_frame(received, 79));
+        assert_eq!(clock.time(), None);
+        assert!(clock.accept_frame(received, 80));
+        assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 37, false)));
+    }
+}
@@ -0,0 +1,416 @@
+use core::fmt;
+#[cfg(feature = "heapless")]
+use core::fmt::Write;
+
+use DCF77Error;
+
+/// A fully decoded DCF77 date/time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTime {
+    /// The year, e.g. 2024
+    pub year: u16,
+    /// The month of the year, 1 meaning January
+    pub month: u8,
+    /// The day of the month, starting at 1
+    pub day: u8,
+    /// The day of the week, 1 meaning Monday and 7 meaning Sunday
+    pub weekday: u8,
+    /// The hour of the day
+    pub hour: u8,
+    /// The minute of the hour
+    pub minute: u8,
+    /// Whether summer time (CEST) is in effect
+    pub cest: bool,
+}
+
+/// The largest difference in seconds `DateTime::correction_to` still slews instead of stepping
+pub const DEFAULT_SLEW_LIMIT_S: i64 = 60;
+
+/// How a local clock should be corrected to match a received date/time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Correction {
+    /// The difference is too large to be slewed, the clock should be set to the received time
+    Step,
+    /// The clock should be gradually adjusted by the given number of seconds, positive meaning
+    /// the clock is behind
+    Slew(i64),
+}
+
+/// Return the number of days since 1970-01-01 for a date in the proleptic Gregorian calendar
+pub(crate) fn days_from_civil(year: i32, month: u8, day: u8) -> i32 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let m = month as i32;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i32 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Return the (year, month, day) of the given number of days since 1970-01-01
+pub(crate) fn civil_from_days(days: i32) -> (i32, u8, u8) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Return the number of days of the month in the given year of the Gregorian calendar
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    /// Return the number of minutes since 1970-01-01 00:00 UTC, taking the CET/CEST offset into
+    /// account
+    pub(crate) fn utc_minutes(&self) -> i64 {
+        let days = days_from_civil(self.year as i32, self.month, self.day) as i64;
+        let offset = if self.cest { 120 } else { 60 };
+
+        days * 1440 + self.hour as i64 * 60 + self.minute as i64 - offset
+    }
+
+    /// Return the date/time the given number of minutes later, keeping the summer time flag and
+    /// recalculating the weekday
+    pub(crate) fn add_minutes(&self, minutes: i64) -> DateTime {
+        let days = days_from_civil(self.year as i32, self.month, self.day) as i64;
+        let total = days * 1440 + self.hour as i64 * 60 + self.minute as i64 + minutes;
+        let days = total.div_euclid(1440);
+        let minute_of_day = total.rem_euclid(1440);
+        let (year, month, day) = civil_from_days(days as i32);
+
+        DateTime {
+            year: year as u16,
+            month,
+            day,
+            weekday: ((days + 3).rem_euclid(7) + 1) as u8,
+            hour: (minute_of_day / 60) as u8,
+            minute: (minute_of_day % 60) as u8,
+            cest: self.cest,
+        }
+    }
+
+    /// Return the hour in 12-hour format together with whether it is PM, i.e. midnight is 12 AM
+    /// and noon is 12 PM
+    pub fn hour_12(&self) -> (u8, bool) {
+        let hour = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+
+        (hour, self.hour >= 12)
+    }
+
+    /// Return the date/time with an overflowing minute or hour carried into the next unit, e.g.
+    /// minute 60 becomes minute 0 of the next hour and hour 24 becomes hour 0 of the next day,
+    /// for display continuity with borderline frames. Returns `None` if the value can't be
+    /// recovered, i.e. the date is invalid or the minute or hour overflows by more than one unit
+    pub fn normalized(self) -> Option<DateTime> {
+        if self.month < 1
+            || self.month > 12
+            || self.day < 1
+            || self.day > days_in_month(self.year, self.month)
+            || self.minute >= 120
+            || self.hour >= 48
+        {
+            return None;
+        }
+
+        Some(self.add_minutes(0))
+    }
+
+    /// Return the next date/time after this one which is a multiple of `cadence_minutes` after
+    /// local midnight, e.g. the next 00:00 or 12:00 for a cadence of 720 minutes, to power up the
+    /// receiver of a duty-cycled device. Cadences which don't divide a day evenly are counted from
+    /// 1970-01-01 00:00 local time instead, a cadence of 0 is treated as 1 minute
+    pub fn next_sync_wake(&self, cadence_minutes: u32) -> DateTime {
+        let cadence = cadence_minutes.max(1) as i64;
+        let days = days_from_civil(self.year as i32, self.month, self.day) as i64;
+        let total = days * 1440 + self.hour as i64 * 60 + self.minute as i64;
+
+        self.add_minutes(cadence - total.rem_euclid(cadence))
+    }
+
+    /// Return the signed number of seconds until the target date/time, negative if the target
+    /// lies in the past. Both are converted to UTC first so differing CET/CEST flags are handled
+    pub fn duration_until(&self, target: &DateTime) -> i64 {
+        (target.utc_minutes() - self.utc_minutes()) * 60
+    }
+
+    /// Return how the local clock showing `current` should be corrected to match this date/time,
+    /// slewing differences of up to `DEFAULT_SLEW_LIMIT_S` seconds and stepping larger ones
+    pub fn correction_to(&self, current: &DateTime) -> Correction {
+        self.correction_to_within(current, DEFAULT_SLEW_LIMIT_S)
+    }
+
+    /// Return how the local clock showing `current` should be corrected to match this date/time,
+    /// slewing differences of up to `max_slew_s` seconds and stepping larger ones
+    pub fn correction_to_within(&self, current: &DateTime, max_slew_s: i64) -> Correction {
+        let diff = current.duration_until(self);
+
+        if diff.abs() > max_slew_s {
+            Correction::Step
+        } else {
+            Correction::Slew(diff)
+        }
+    }
+
+    /// Return the date/time as ISO 8601 string with the CET/CEST offset, like
+    /// `2024-03-15T13:37:00+01:00`
+    #[cfg(feature = "heapless")]
+    pub fn to_iso8601_string(&self) -> heapless::String<32> {
+        let mut s = heapless::String::new();
+
+        // The longest possible output has 25 characters so this can't fail
+        let _ = write!(
+            s,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:00+0{}:00",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            if self.cest { 2 } else { 1 }
+        );
+
+        s
+    }
+
+    /// Write the date/time as a compact JSON object like
+    /// `{"year":2024,"month":3,"day":15,"weekday":5,"hour":13,"minute":37,"cest":false}` without
+    /// requiring any allocation
+    pub fn write_json<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(
+            w,
+            "{{\"year\":{},\"month\":{},\"day\":{},\"weekday\":{},\"hour\":{},\"minute\":{},\"cest\":{}}}",
+            self.year, self.month, self.day, self.weekday, self.hour, self.minute, self.cest
+        )
+    }
+}
+
+/// Show the date/time like `2024-03-31 02:59 CEST`
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02} {}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            if self.cest { "CEST" } else { "CET" }
+        )
+    }
+}
+
+/// Options controlling the interpretation and validation of a frame, see
+/// `DCF77Time::decode_with_options`. The default options decode identically to `DCF77Time::decode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// The century the transmitted two-digit year is added to, e.g. 1900 for archival captures
+    /// from before 2000
+    pub century: u16,
+    /// Whether the date also has to exist in the calendar, i.e. month, day and weekday must not be
+    /// 0 and the day must not exceed the length of the month in the leap year rules of the year
+    pub strict: bool,
+    /// Whether the transmitted weekday has to match the weekday of the date in the given century
+    pub check_weekday: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            century: 2000,
+            strict: false,
+            check_weekday: false,
+        }
+    }
+}
+
+/// The independently validated fields of a DCF77 frame, see `DCF77Time::decode_partial`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialDecode {
+    /// The result of the start bit validation
+    pub start: Result<(), DCF77Error>,
+    /// Whether summer time (CEST) is in effect
+    pub cest: Result<bool, DCF77Error>,
+    /// The minute of the hour
+    pub minute: Result<u8, DCF77Error>,
+    /// The hour of the day
+    pub hour: Result<u8, DCF77Error>,
+    /// The date as tuple of (year, month, day, weekday)
+    pub date: Result<(u16, u8, u8, u8), DCF77Error>,
+}
+
+/// The individually validated fields of a DCF77 frame, see `DCF77Time::decode_verbose`. The date
+/// fields share a parity bit, so a parity mismatch marks all of them while a value out of range
+/// only marks the affected field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeReport {
+    /// The minute of the hour
+    pub minute: Result<u8, DCF77Error>,
+    /// The hour of the day
+    pub hour: Result<u8, DCF77Error>,
+    /// The day of the month
+    pub day: Result<u8, DCF77Error>,
+    /// The month of the year
+    pub month: Result<u8, DCF77Error>,
+    /// The day of the week, 1 meaning Monday and 7 meaning Sunday
+    pub weekday: Result<u8, DCF77Error>,
+    /// The year
+    pub year: Result<u16, DCF77Error>,
+    /// Whether summer time (CEST) is in effect
+    pub cest: Result<bool, DCF77Error>,
+}
+
+/// Everything known about a DCF77 frame, see `DCF77Time::interpret`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interpretation {
+    /// The best-effort date/time built from the unchecked fields
+    pub time: DateTime,
+    /// The independently validated fields
+    pub fields: PartialDecode,
+    /// The results of all validation checks, see `DCF77Time::validity_mask`
+    pub validity: u16,
+    /// Whether the frame decodes and passes all validation checks
+    pub is_trustworthy: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+
+    use testutil::*;
+    use {Correction, DCF77Time, DateTime};
+
+    #[test]
+    fn duration_until_across_a_day_boundary() {
+        let before = time(2024, 3, 15, 23, 58, false);
+        let after = time(2024, 3, 16, 0, 3, false);
+
+        assert_eq!(before.duration_until(&after), 300);
+        assert_eq!(after.duration_until(&before), -300);
+        assert_eq!(before.duration_until(&before), 0);
+
+        let leap = time(2024, 2, 28, 12, 0, false);
+        assert_eq!(leap.duration_until(&time(2024, 3, 1, 12, 0, false)), 172_800);
+
+        // The CET/CEST switch: 01:59 CET is followed by 03:00 CEST
+        let switch = time(2024, 3, 31, 1, 59, false);
+        assert_eq!(switch.duration_until(&time(2024, 3, 31, 3, 0, true)), 60);
+
+        // 01:30 CEST is 23:30 UTC of the previous day
+        let summer = time(2024, 7, 2, 1, 30, true);
+        assert_eq!(summer.duration_until(&time(2024, 7, 1, 23, 45, true)), -6300);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn iso8601_string_with_the_utc_offset() {
+        assert_eq!(
+            time(2024, 3, 15, 13, 37, false).to_iso8601_string().as_str(),
+            "2024-03-15T13:37:00+01:00"
+        );
+        assert_eq!(
+            time(2024, 7, 1, 0, 5, true).to_iso8601_string().as_str(),
+            "2024-07-01T00:05:00+02:00"
+        );
+    }
+
+    #[test]
+    fn normalized_carries_an_overflowing_minute_and_hour() {
+        let mut borderline = time(2024, 12, 31, 23, 0, false);
+        borderline.minute = 60;
+        assert_eq!(borderline.normalized(), Some(time(2025, 1, 1, 0, 0, false)));
+
+        let mut borderline = time(2024, 2, 28, 0, 15, true);
+        borderline.hour = 24;
+        assert_eq!(borderline.normalized(), Some(time(2024, 2, 29, 0, 15, true)));
+
+        let valid = time(2024, 3, 15, 13, 37, false);
+        assert_eq!(valid.normalized(), Some(valid));
+
+        let mut overflow = valid;
+        overflow.minute = 120;
+        assert_eq!(overflow.normalized(), None);
+        let mut overflow = valid;
+        overflow.hour = 48;
+        assert_eq!(overflow.normalized(), None);
+        let mut invalid = valid;
+        invalid.day = 32;
+        assert_eq!(invalid.normalized(), None);
+    }
+
+    #[test]
+    fn correction_slews_small_and_steps_large_differences() {
+        let received = time(2024, 3, 15, 13, 37, false);
+
+        let correction = |current: DateTime| received.correction_to(&current);
+
+        assert_eq!(correction(received), Correction::Slew(0));
+        assert_eq!(correction(time(2024, 3, 15, 13, 36, false)), Correction::Slew(60));
+        assert_eq!(correction(time(2024, 3, 15, 13, 38, false)), Correction::Slew(-60));
+        assert_eq!(correction(time(2024, 3, 15, 13, 35, false)), Correction::Step);
+        assert_eq!(correction(time(2024, 3, 15, 14, 37, true)), Correction::Slew(0));
+        assert_eq!(correction(time(2024, 3, 14, 13, 37, false)), Correction::Step);
+
+        let behind = time(2024, 3, 15, 13, 30, false);
+        assert_eq!(received.correction_to_within(&behind, 420), Correction::Slew(420));
+        assert_eq!(received.correction_to_within(&behind, 419), Correction::Step);
+    }
+
+    #[test]
+    fn next_sync_wake_with_a_12_hour_cadence() {
+        let wake = |from: DateTime| from.next_sync_wake(720);
+
+        assert_eq!(wake(time(2024, 3, 15, 0, 0, false)), time(2024, 3, 15, 12, 0, false));
+        assert_eq!(wake(time(2024, 3, 15, 11, 59, false)), time(2024, 3, 15, 12, 0, false));
+        assert_eq!(wake(time(2024, 3, 15, 12, 0, false)), time(2024, 3, 16, 0, 0, false));
+        assert_eq!(wake(time(2024, 12, 31, 13, 37, false)), time(2025, 1, 1, 0, 0, false));
+        assert_eq!(wake(time(2024, 2, 28, 23, 0, true)), time(2024, 2, 29, 0, 0, true));
+
+        // A cadence of 0 wakes up the next minute
+        let now = time(2024, 3, 15, 13, 37, false);
+        assert_eq!(now.next_sync_wake(0), time(2024, 3, 15, 13, 38, false));
+    }
+
+    #[test]
+    fn hour_12_around_midnight_and_noon() {
+        assert_eq!(time(2024, 3, 15, 0, 0, false).hour_12(), (12, false));
+        assert_eq!(time(2024, 3, 15, 1, 0, false).hour_12(), (1, false));
+        assert_eq!(time(2024, 3, 15, 11, 59, false).hour_12(), (11, false));
+        assert_eq!(time(2024, 3, 15, 12, 0, false).hour_12(), (12, true));
+        assert_eq!(time(2024, 3, 15, 13, 37, false).hour_12(), (1, true));
+        assert_eq!(time(2024, 3, 15, 23, 0, false).hour_12(), (11, true));
+    }
+
+    #[test]
+    fn write_json_of_decoded_frame() {
+        let decoded = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false))).decode();
+        let mut json = String::new();
+        decoded.unwrap().write_json(&mut json).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"year":2024,"month":3,"day":15,"weekday":5,"hour":13,"minute":37,"cest":false}"#
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use {DCF77Time, SimpleDCF77Decoder};
+
+/// How a `DualDecoder` combines the frames of its two receivers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DualMode {
+    /// Take the valid frame with the higher aggregate bit confidence
+    HigherConfidence,
+    /// Only take a frame if both receivers decoded the same valid frame
+    RequireAgreement,
+}
+
+/// A decoder for redundant installations with two DCF77 receivers
+pub struct DualDecoder {
+    decoders: [SimpleDCF77Decoder; 2],
+    pending: [Option<(u64, u8)>; 2],
+    completed: [bool; 2],
+    pendingage: u16,
+    mode: DualMode,
+    frame: Option<DCF77Time>,
+}
+
+/// The DualDecoder feeds the samples of both receivers into one `SimpleDCF77Decoder` each. Once a
+/// receiver completes a cycle, the other one gets up to one second to complete its cycle as well,
+/// since the receivers may have different delays. The frames of both are then combined according
+/// to the `DualMode` and the result can be fetched with `take_frame`. A frame only counts if it
+/// passes all checks of `DCF77Time::decode`, so in `HigherConfidence` mode a corrupt frame of one
+/// receiver is ignored in favour of the other one
+impl DualDecoder {
+    /// Create a new dual decoder expecting a sample of both receivers every 10ms
+    pub fn new(mode: DualMode) -> Self {
+        Self::with_sample_period_ms(mode, 10)
+    }
+
+    /// Create a new dual decoder expecting a sample of both receivers every `period_ms`
+    /// milliseconds, see `SimpleDCF77Decoder::with_sample_period_ms`
+    pub fn with_sample_period_ms(mode: DualMode, period_ms: u16) -> Self {
+        Self {
+            decoders: [
+                SimpleDCF77Decoder::with_sample_period_ms(period_ms),
+                SimpleDCF77Decoder::with_sample_period_ms(period_ms),
+            ],
+            pending: [None, None],
+            completed: [false, false],
+            pendingage: 0,
+            mode,
+            frame: None,
+        }
+    }
+
+    /// Return a reference to the decoder of the first (`0`) or second (`1`) receiver
+    pub fn decoder(&self, receiver: usize) -> &SimpleDCF77Decoder {
+        &self.decoders[receiver]
+    }
+
+    /// Ingest the latest samples of both receivers, see `SimpleDCF77Decoder::read_bit`
+    pub fn read_bits(&mut self, first: bool, second: bool) {
+        self.decoders[0].read_bit(first);
+        self.decoders[1].read_bit(second);
+
+        for receiver in 0..2 {
+            let decoder = &self.decoders[receiver];
+            if decoder.cycle_ended() && (58..=60).contains(&decoder.cycle_length()) {
+                let frame = decoder.received_frame();
+                self.completed[receiver] = true;
+                self.pending[receiver] = match frame.decode() {
+                    Ok(_) => Some((frame.bits(), decoder.frame_confidence())),
+                    Err(_) => None,
+                };
+            }
+        }
+
+        if !self.completed[0] && !self.completed[1] {
+            return;
+        }
+
+        self.pendingage += 1;
+        let timeout = 1000 / self.decoders[0].sample_period_ms();
+        if (self.completed[0] && self.completed[1]) || self.pendingage > timeout {
+            self.frame = self.combine();
+            self.pending = [None, None];
+            self.completed = [false, false];
+            self.pendingage = 0;
+        }
+    }
+
+    /// Combine the pending frames of both receivers according to the mode
+    fn combine(&self) -> Option<DCF77Time> {
+        let bits = match (self.mode, self.pending[0], self.pending[1]) {
+            (DualMode::HigherConfidence, Some(first), Some(second)) => {
+                if second.1 > first.1 {
+                    second.0
+                } else {
+                    first.0
+                }
+            }
+            (DualMode::HigherConfidence, Some(frame), None)
+            | (DualMode::HigherConfidence, None, Some(frame)) => frame.0,
+            (DualMode::RequireAgreement, Some(first), Some(second)) if first.0 == second.0 => {
+                first.0
+            }
+            _ => return None,
+        };
+
+        Some(DCF77Time::new(bits))
+    }
+
+    /// Return the latest combined frame exactly once
+    pub fn take_frame(&mut self) -> Option<DCF77Time> {
+        self.frame.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use testutil::*;
+    use {DualDecoder, DualMode};
+
+    /// Feed both signals, the second one delayed by `delay` samples, and return the taken frames
+    fn feed(mode: DualMode, first: &[bool], second: &[bool], delay: usize) -> Vec<u64> {
+        let mut decoder = DualDecoder::new(mode);
+        let mut frames = Vec::new();
+        for index in 0..first.len() + delay {
+            let delayed = index.checked_sub(delay).and_then(|index| second.get(index));
+            decoder.read_bits(first.get(index) == Some(&true), delayed == Some(&true));
+            frames.extend(decoder.take_frame().map(|frame| frame.bits()));
+        }
+
+        frames
+    }
+
+    #[test]
+    fn corrupt_receiver_is_ignored_in_favour_of_the_other() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let good = signal(&[sent]);
+        let corrupt = signal(&[sent ^ (1 << 58)]);
+
+        assert_eq!(feed(DualMode::HigherConfidence, &good, &corrupt, 30), [sent]);
+        assert_eq!(feed(DualMode::HigherConfidence, &corrupt, &good, 30), [sent]);
+        assert_eq!(feed(DualMode::RequireAgreement, &good, &corrupt, 30), []);
+        assert_eq!(feed(DualMode::RequireAgreement, &good, &good, 30), [sent]);
+    }
+}
@@ -0,0 +1,129 @@
+use {DCF77Time, SimpleDCF77Decoder};
+
+/// A decoder for interrupt driven designs which only get notified about signal edges
+pub struct EdgeSampleDecoder {
+    decoder: SimpleDCF77Decoder,
+    level: bool,
+    nextsample: u32,
+    started: bool,
+    frame: Option<DCF77Time>,
+}
+
+impl Default for EdgeSampleDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The EdgeSampleDecoder reconstructs the signal level every sample period of its
+/// `SimpleDCF77Decoder`, 10ms by default, from timestamped edge transitions and feeds it to the
+/// decoder, so pulse widths are classified with the same thresholds as polled samples. To use
+/// this, call the `edge` method from the GPIO interrupt handler for both edges with a monotonic
+/// millisecond timestamp, which may wrap around, and the new signal level. Since samples are
+/// only generated when an edge arrives, a completed frame is latched and can be fetched with
+/// `take_frame` at any time
+impl EdgeSampleDecoder {
+    /// Create a new edge based decoder
+    pub fn new() -> Self {
+        Self::with_decoder(SimpleDCF77Decoder::new())
+    }
+
+    /// Create a new edge based decoder feeding the given decoder, e.g. one created with
+    /// `SimpleDCF77Decoder::builder` to use a different sample period or bit thresholds
+    pub fn with_decoder(decoder: SimpleDCF77Decoder) -> Self {
+        Self {
+            decoder,
+            level: false,
+            nextsample: 0,
+            started: false,
+            frame: None,
+        }
+    }
+
+    /// Return a reference to the underlying decoder
+    pub fn decoder(&self) -> &SimpleDCF77Decoder {
+        &self.decoder
+    }
+
+    /// Ingest an edge of the signal at the given timestamp, `level` being the signal level after
+    /// the edge. This is the counterpart of `SimpleDCF77Decoder::read_bit` for edge driven designs
+    pub fn edge(&mut self, timestamp_ms: u32, level: bool) {
+        self.advance(timestamp_ms);
+        self.level = level;
+    }
+
+    /// Feed all samples up to the given timestamp with the current signal level into the state
+    /// machine, e.g. to detect the end of a cycle without waiting for the next edge
+    pub fn advance(&mut self, timestamp_ms: u32) {
+        if !self.started {
+            self.started = true;
+            self.nextsample = timestamp_ms;
+            return;
+        }
+
+        while (timestamp_ms.wrapping_sub(self.nextsample) as i32) > 0 {
+            self.decoder.read_bit(self.level);
+            if self.decoder.end_of_cycle() {
+                self.frame = Some(self.decoder.received_frame());
+            }
+            self.nextsample = self
+                .nextsample
+                .wrapping_add(self.decoder.sample_period_ms() as u32);
+        }
+    }
+
+    /// Return the latest completed frame exactly once
+    pub fn take_frame(&mut self) -> Option<DCF77Time> {
+        self.frame.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testutil::*;
+    use {EdgeSampleDecoder, SimpleDCF77Decoder};
+
+    /// Feed the level changes of the samples taken every 10ms as edges, the first sample at `start`
+    fn feed_edges(decoder: &mut EdgeSampleDecoder, samples: &[bool], start: u32) -> u32 {
+        let mut level = false;
+        let mut timestamp = start;
+        decoder.edge(timestamp, level);
+
+        for &sample in samples {
+            if sample != level {
+                level = sample;
+                decoder.edge(timestamp, level);
+            }
+            timestamp = timestamp.wrapping_add(10);
+        }
+
+        timestamp
+    }
+
+    #[test]
+    fn edges_decode_to_a_frame() {
+        let sent = time(2024, 3, 15, 13, 37, false);
+        let mut decoder = EdgeSampleDecoder::new();
+
+        let end = feed_edges(&mut decoder, &signal(&[frame(&sent)]), 5000);
+        assert_eq!(decoder.take_frame(), None);
+
+        decoder.advance(end);
+        assert_eq!(decoder.take_frame().map(|frame| frame.decode()), Some(Ok(sent)));
+        assert_eq!(decoder.take_frame(), None);
+    }
+
+    #[test]
+    fn timestamps_wrapping_around_mid_frame() {
+        let sent = time(2024, 3, 15, 13, 37, false);
+        let builder = SimpleDCF77Decoder::builder().sample_period_ms(20);
+        let mut decoder = EdgeSampleDecoder::with_decoder(builder.build());
+
+        let start = u32::MAX - 30_000;
+        let end = feed_edges(&mut decoder, &signal(&[frame(&sent)]), start);
+        assert!(end < start);
+
+        decoder.advance(end);
+        assert_eq!(decoder.take_frame().map(|frame| frame.decode()), Some(Ok(sent)));
+    }
+}
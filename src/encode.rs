@@ -0,0 +1,135 @@
+use {DCF77Time, DateTime};
+
+/// The reference date/time of the self test, 2024-03-15 (Friday) 13:37 CET
+const REFERENCE_TIME: DateTime = DateTime {
+    year: 2024,
+    month: 3,
+    day: 15,
+    weekday: 5,
+    hour: 13,
+    minute: 37,
+    cest: false,
+};
+
+/// The frame transmitted for the reference date/time
+const REFERENCE_FRAME: u64 = 0x0490_755a_76f4_0000;
+
+/// Return the value in BCD, the units in the low nibble and the tens above
+fn bcd(value: u8) -> u64 {
+    ((value / 10) << 4 | (value % 10)) as u64
+}
+
+impl DCF77Time {
+    /// Generate the frame transmitted for the given date/time, with the year being taken modulo
+    /// 100 and all parity bits set accordingly
+    pub(crate) fn encode(time: &DateTime) -> Self {
+        let mut bits = 1 << 20;
+        bits |= if time.cest { 1 << 17 } else { 1 << 18 };
+        bits |= bcd(time.minute) << 21;
+        bits |= bcd(time.hour) << 29;
+        bits |= bcd(time.day) << 36;
+        bits |= (time.weekday as u64) << 42;
+        bits |= bcd(time.month) << 45;
+        bits |= bcd((time.year % 100) as u8) << 50;
+
+        let mut frame = DCF77Time::new(bits);
+        for &(start, end) in &[(21, 28), (29, 35), (36, 58)] {
+            if frame.calculate_parity(start, end) {
+                frame.data |= 1 << end;
+            }
+        }
+
+        frame
+    }
+
+    /// Encode a reference date/time, check the frame bit by bit and decode it again to verify the
+    /// decoding logic is intact, e.g. as part of a power-on self test. Returns true on success
+    pub fn self_test() -> bool {
+        let frame = DCF77Time::encode(&REFERENCE_TIME);
+        let corrupt = DCF77Time::new(REFERENCE_FRAME ^ (1 << 58));
+
+        frame.bits() == REFERENCE_FRAME
+            && frame.decode() == Ok(REFERENCE_TIME)
+            && frame.decode_fast() == Ok(REFERENCE_TIME)
+            && frame.validate_weekday_consistency().is_ok()
+            && corrupt.decode().is_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use testutil::*;
+    use {DCF77Time, DateTime};
+
+    /// The last minute of the century, 2099-12-31 (Thursday) 23:59 CET, with the largest digits of
+    /// all BCD fields
+    const CENTURY_END: DateTime = DateTime {
+        year: 2099,
+        month: 12,
+        day: 31,
+        weekday: 4,
+        hour: 23,
+        minute: 59,
+        cest: false,
+    };
+
+    #[test]
+    fn self_test_passes() {
+        assert!(DCF77Time::self_test());
+    }
+
+    #[test]
+    fn century_end_round_trips() {
+        let frame = DCF77Time::encode(&CENTURY_END);
+
+        assert_eq!(frame.decode(), Ok(CENTURY_END));
+        assert_eq!(frame.validate_weekday_consistency(), Ok(()));
+    }
+
+    #[test]
+    fn known_frames_decode_and_encode() {
+        // Frames assembled bit by bit from the DCF77 bit layout independently of `encode`,
+        // together with the date/time they carry
+        let known = [
+            (0x0490_755a_76f4_0000, (2024, 3, 15, 5, 13, 37, false)),
+            (0x0494_2c10_0014_0000, (2025, 1, 1, 3, 0, 0, false)),
+            (0x0490_e412_4612_0000, (2024, 7, 1, 1, 12, 30, true)),
+            (0x0266_531c_6b34_0000, (2099, 12, 31, 4, 23, 59, false)),
+            (0x0400_4a91_20b4_0000, (2000, 2, 29, 2, 9, 5, false)),
+        ];
+
+        for &(bits, (year, month, day, weekday, hour, minute, cest)) in &known {
+            let expected = DateTime {
+                year,
+                month,
+                day,
+                weekday,
+                hour,
+                minute,
+                cest,
+            };
+
+            assert_eq!(DCF77Time::new(bits).decode(), Ok(expected), "{:#x}", bits);
+            assert_eq!(DCF77Time::encode(&expected).bits(), bits, "{:?}", expected);
+        }
+    }
+
+    #[test]
+    fn every_field_value_round_trips() {
+        let mut times = Vec::new();
+        times.extend((0..60).map(|minute| time(2024, 3, 15, 13, minute, false)));
+        times.extend((0..24).map(|hour| time(2024, 3, 15, hour, 37, true)));
+        times.extend((1..=31).map(|day| time(2024, 1, day, 13, 37, false)));
+        times.extend((1..=12).map(|month| time(2024, month, 28, 13, 37, false)));
+        times.extend((2000..2100).map(|year| time(year, 3, 15, 13, 37, false)));
+
+        for expected in times {
+            let frame = DCF77Time::encode(&expected);
+
+            assert_eq!(frame.decode(), Ok(expected));
+            assert_eq!(frame.validate_weekday_consistency(), Ok(()));
+        }
+    }
+}
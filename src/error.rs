@@ -0,0 +1,23 @@
+use FieldKind;
+
+/// The reasons why validating a DCF77 frame can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DCF77Error {
+    /// The start bit (bit 0) is set although it always has to be 0
+    StartBitSet,
+    /// The summer time bit (bit 17) and its counterpart (bit 18) are not complementary
+    TimezoneInconsistent,
+    /// The transmitted parity bit of a field doesn't match the calculated parity
+    ParityMismatch,
+    /// The decoded value of the given field is outside of its valid range
+    ValueOutOfRange(FieldKind),
+    /// The transmitted weekday doesn't match the weekday of the transmitted calendar date
+    WeekdayInconsistent,
+    /// Not all bits needed to verify the field, including its parity bit, were received
+    FrameIncomplete,
+    /// The leap second announcement bit (bit 19) is set outside of the hour before a possible
+    /// leap second
+    LeapSecondInconsistent,
+}
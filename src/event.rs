@@ -0,0 +1,66 @@
+/// The number of events the decoder event log keeps before overwriting the oldest one
+pub const EVENT_LOG_CAPACITY: usize = 64;
+
+/// A state change of the decoder
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecoderEvent {
+    /// The rising edge starting a second was found
+    PhaseFound,
+    /// A bit with the given value was received
+    BitReceived {
+        /// The value of the received bit
+        value: bool,
+    },
+    /// The pulse couldn't be recognized as either bit value
+    FaultyBit,
+    /// The minute marker ending a cycle was detected
+    EndOfCycle,
+}
+
+/// A decoder event together with the index of the sample which caused it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// The index of the sample, counted from the creation of the decoder and wrapping around
+    pub sample: u32,
+    /// The recorded event
+    pub event: DecoderEvent,
+}
+
+/// A ring buffer keeping the latest `EVENT_LOG_CAPACITY` decoder events
+pub(crate) struct EventLog {
+    events: [LoggedEvent; EVENT_LOG_CAPACITY],
+    next: usize,
+    len: usize,
+}
+
+impl EventLog {
+    pub(crate) const fn new() -> Self {
+        Self {
+            events: [LoggedEvent {
+                sample: 0,
+                event: DecoderEvent::PhaseFound,
+            }; EVENT_LOG_CAPACITY],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Record an event, overwriting the oldest one if the log is full
+    pub(crate) fn push(&mut self, sample: u32, event: DecoderEvent) {
+        self.events[self.next] = LoggedEvent { sample, event };
+        self.next = (self.next + 1) % EVENT_LOG_CAPACITY;
+        if self.len < EVENT_LOG_CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Return an iterator over the recorded events from the oldest to the newest one
+    pub(crate) fn iter(&self) -> impl Iterator<Item = LoggedEvent> + '_ {
+        let first = self.next + EVENT_LOG_CAPACITY - self.len;
+        (0..self.len).map(move |i| self.events[(first + i) % EVENT_LOG_CAPACITY])
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+}
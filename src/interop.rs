@@ -0,0 +1,246 @@
+#[cfg(any(feature = "chrono", feature = "time"))]
+use core::convert::TryFrom;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime as ChronoDateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "time")]
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use DCF77Time;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use {DCF77Error, DateTime, FieldKind};
+
+/// Return the date/time as chrono `NaiveDateTime` in local CET/CEST time
+#[cfg(feature = "chrono")]
+fn naive_date_time(time: &DateTime) -> Result<NaiveDateTime, DCF77Error> {
+    NaiveDate::from_ymd_opt(time.year as i32, time.month as u32, time.day as u32)
+        .and_then(|date| date.and_hms_opt(time.hour as u32, time.minute as u32, 0))
+        .ok_or(DCF77Error::ValueOutOfRange(FieldKind::Date))
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DCF77Time> for NaiveDateTime {
+    type Error = DCF77Error;
+
+    /// Decode the frame with all checks of `DCF77Time::decode` into the local CET/CEST date/time
+    fn try_from(frame: DCF77Time) -> Result<Self, Self::Error> {
+        naive_date_time(&frame.decode()?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DCF77Time> for ChronoDateTime<FixedOffset> {
+    type Error = DCF77Error;
+
+    /// Decode the frame with all checks of `DCF77Time::decode` into the date/time with a fixed
+    /// offset of +02:00 if summer time (CEST) is in effect and +01:00 otherwise
+    fn try_from(frame: DCF77Time) -> Result<Self, Self::Error> {
+        let time = frame.decode()?;
+        let naive = naive_date_time(&time)?;
+        let offset = if time.cest { 2 * 3600 } else { 3600 };
+
+        FixedOffset::east_opt(offset)
+            .and_then(|offset| offset.from_local_datetime(&naive).single())
+            .ok_or(DCF77Error::ValueOutOfRange(FieldKind::Date))
+    }
+}
+
+/// Return the date/time as time `PrimitiveDateTime` in local CET/CEST time
+#[cfg(feature = "time")]
+fn primitive_date_time(time: &DateTime) -> Result<PrimitiveDateTime, DCF77Error> {
+    let error = DCF77Error::ValueOutOfRange(FieldKind::Date);
+    let month = Month::try_from(time.month).map_err(|_| error)?;
+    let date = Date::from_calendar_date(time.year as i32, month, time.day).map_err(|_| error)?;
+    let clock = Time::from_hms(time.hour, time.minute, 0).map_err(|_| error)?;
+
+    Ok(PrimitiveDateTime::new(date, clock))
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<DCF77Time> for PrimitiveDateTime {
+    type Error = DCF77Error;
+
+    /// Decode the frame with all checks of `DCF77Time::decode` into the local CET/CEST date/time
+    fn try_from(frame: DCF77Time) -> Result<Self, Self::Error> {
+        primitive_date_time(&frame.decode()?)
+    }
+}
+
+#[cfg(feature = "time")]
+impl DCF77Time {
+    /// Decode the frame with all checks of `DCF77Time::decode` into a time `OffsetDateTime` with
+    /// an offset of +02:00 if summer time (CEST) is in effect and +01:00 otherwise
+    pub fn to_offset_date_time(&self) -> Result<OffsetDateTime, DCF77Error> {
+        let time = self.decode()?;
+        let offset = UtcOffset::from_hms(if time.cest { 2 } else { 1 }, 0, 0)
+            .map_err(|_| DCF77Error::ValueOutOfRange(FieldKind::Date))?;
+
+        Ok(primitive_date_time(&time)?.assume_offset(offset))
+    }
+}
+
+/// Serialize the frame as its raw bits
+#[cfg(feature = "serde")]
+impl Serialize for DCF77Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+/// Deserialize a frame from its raw bits as a completely received frame like `DCF77Time::new`
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DCF77Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(DCF77Time::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    use core::convert::TryFrom;
+    use testutil::*;
+    use DCF77Time;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_offset_follows_summer_time() {
+        use chrono::{DateTime as ChronoDateTime, FixedOffset, NaiveDate, NaiveDateTime};
+
+        let winter = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let converted = ChronoDateTime::<FixedOffset>::try_from(winter).unwrap();
+        assert_eq!(converted.offset().local_minus_utc(), 3600);
+        assert_eq!(converted.timestamp(), 1_710_506_220);
+
+        let summer = DCF77Time::new(frame(&time(2024, 7, 1, 12, 0, true)));
+        let converted = ChronoDateTime::<FixedOffset>::try_from(summer).unwrap();
+        assert_eq!(converted.offset().local_minus_utc(), 2 * 3600);
+        assert_eq!(converted.timestamp(), 1_719_828_000);
+
+        let naive = NaiveDate::from_ymd_opt(2024, 7, 1).and_then(|date| date.and_hms_opt(12, 0, 0));
+        assert_eq!(NaiveDateTime::try_from(summer).ok(), naive);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_offset_follows_summer_time() {
+        use time::PrimitiveDateTime;
+
+        let winter = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let converted = winter.to_offset_date_time().unwrap();
+        assert_eq!(converted.offset().whole_hours(), 1);
+        assert_eq!(converted.unix_timestamp(), 1_710_506_220);
+
+        let summer = DCF77Time::new(frame(&time(2024, 7, 1, 12, 0, true)));
+        let converted = summer.to_offset_date_time().unwrap();
+        assert_eq!(converted.offset().whole_hours(), 2);
+        assert_eq!(converted.unix_timestamp(), 1_719_828_000);
+
+        let primitive = PrimitiveDateTime::try_from(summer).unwrap();
+        assert_eq!((primitive.hour(), primitive.minute()), (12, 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_keeps_the_bits() {
+        use serde::de::value::{Error, U64Deserializer};
+        use serde::de::IntoDeserializer;
+        use serde::{Deserialize, Serialize};
+
+        let original = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let bits = original.serialize(RawBits).unwrap();
+        assert_eq!(bits, original.bits());
+
+        let deserializer: U64Deserializer<Error> = bits.into_deserializer();
+        let restored = DCF77Time::deserialize(deserializer).unwrap();
+        assert_eq!(restored.bits(), original.bits());
+        assert_eq!(restored.decode(), original.decode());
+    }
+
+    /// Serializer only accepting the raw `u64` bits a `DCF77Time` serializes to
+    #[cfg(feature = "serde")]
+    struct RawBits;
+
+    #[cfg(feature = "serde")]
+    macro_rules! unsupported {
+        ($($name:ident($($arg:ty),*) -> $ret:ty;)*) => {
+            $(fn $name(self, $(_: $arg),*) -> Result<$ret, Self::Error> {
+                Err(serde::ser::Error::custom("unsupported"))
+            })*
+        };
+    }
+
+    #[cfg(feature = "serde")]
+    impl serde::Serializer for RawBits {
+        type Ok = u64;
+        type Error = serde::de::value::Error;
+        type SerializeSeq = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeTuple = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeTupleStruct = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeTupleVariant = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeMap = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeStruct = serde::ser::Impossible<u64, Self::Error>;
+        type SerializeStructVariant = serde::ser::Impossible<u64, Self::Error>;
+
+        fn serialize_u64(self, value: u64) -> Result<u64, Self::Error> {
+            Ok(value)
+        }
+
+        fn serialize_some<T: serde::Serialize + ?Sized>(self, _: &T) -> Result<u64, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: &T,
+        ) -> Result<u64, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<u64, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn collect_str<T: core::fmt::Display + ?Sized>(self, _: &T) -> Result<u64, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        unsupported! {
+            serialize_bool(bool) -> u64;
+            serialize_i8(i8) -> u64;
+            serialize_i16(i16) -> u64;
+            serialize_i32(i32) -> u64;
+            serialize_i64(i64) -> u64;
+            serialize_u8(u8) -> u64;
+            serialize_u16(u16) -> u64;
+            serialize_u32(u32) -> u64;
+            serialize_f32(f32) -> u64;
+            serialize_f64(f64) -> u64;
+            serialize_char(char) -> u64;
+            serialize_str(&str) -> u64;
+            serialize_bytes(&[u8]) -> u64;
+            serialize_none() -> u64;
+            serialize_unit() -> u64;
+            serialize_unit_struct(&'static str) -> u64;
+            serialize_unit_variant(&'static str, u32, &'static str) -> u64;
+            serialize_seq(Option<usize>) -> Self::SerializeSeq;
+            serialize_tuple(usize) -> Self::SerializeTuple;
+            serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+            serialize_tuple_variant(&'static str, u32, &'static str, usize)
+                -> Self::SerializeTupleVariant;
+            serialize_map(Option<usize>) -> Self::SerializeMap;
+            serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+            serialize_struct_variant(&'static str, u32, &'static str, usize)
+                -> Self::SerializeStructVariant;
+        }
+    }
+}
@@ -1,26 +1,151 @@
 //! A simple GPIO based DCF77 decoder
 //!
-//! This driver was built using [`embedded-hal`] traits.
+//! This driver was built using [`embedded-hal`] traits. With the `embedded-hal` feature enabled
+//! the decoder can read the input pin the receiver is connected to by itself.
 //!
-//! [`embedded-hal`]: https://docs.rs/embedded-hal/~0.2
+//! [`embedded-hal`]: https://docs.rs/embedded-hal/~1.0
 
 #![deny(warnings)]
 #![no_std]
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "embedded-hal")]
+extern crate embedded_hal;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(test)]
+#[macro_use]
+extern crate std;
+#[cfg(feature = "time")]
+extern crate time;
+
+mod builder;
+mod clock;
+mod datetime;
+mod dual;
+mod edge;
+mod encode;
+mod error;
+#[cfg(feature = "event-log")]
+mod event;
+#[cfg(any(feature = "chrono", feature = "serde", feature = "time"))]
+mod interop;
+#[cfg(test)]
+mod testutil;
+mod transport;
+
+pub use builder::SimpleDCF77DecoderBuilder;
+pub use clock::DCF77Clock;
+pub use datetime::{
+    Correction, DateTime, DecodeOptions, DecodeReport, Interpretation, PartialDecode,
+    DEFAULT_SLEW_LIMIT_S,
+};
+pub use dual::{DualDecoder, DualMode};
+pub use edge::EdgeSampleDecoder;
+pub use error::DCF77Error;
+#[cfg(feature = "event-log")]
+pub use event::{DecoderEvent, LoggedEvent, EVENT_LOG_CAPACITY};
+pub use transport::TransportError;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::fmt::Write;
+use core::mem;
+
+use datetime::{days_from_civil, days_in_month};
+#[cfg(feature = "embedded-hal")]
+use embedded_hal::digital::InputPin;
+#[cfg(feature = "event-log")]
+use event::EventLog;
+
 /// A structure to facilitate the decoding of a DCF77 signal which consists of 59 consecutive bits
-/// of data 
-pub struct DCF77Time(pub u64);
+/// of data, together with the number of bits which were actually received. Two frames are equal if
+/// both the bits and the number of received bits match
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DCF77Time {
+    data: u64,
+    received: u8,
+}
 
 impl DCF77Time {
+    /// Bit of the `validity_mask` set if the start bit is 0
+    pub const VALID_START: u16 = 1 << 0;
+    /// Bit of the `validity_mask` set if the begin of time information (bit 20) is 1
+    pub const VALID_FRAMING: u16 = 1 << 1;
+    /// Bit of the `validity_mask` set if the minute parity is correct
+    pub const VALID_MINUTE_PARITY: u16 = 1 << 2;
+    /// Bit of the `validity_mask` set if the hour parity is correct
+    pub const VALID_HOUR_PARITY: u16 = 1 << 3;
+    /// Bit of the `validity_mask` set if the date parity is correct
+    pub const VALID_DATE_PARITY: u16 = 1 << 4;
+    /// Bit of the `validity_mask` set if the weekday matches the calendar date
+    pub const VALID_WEEKDAY: u16 = 1 << 5;
+    /// Bit of the `validity_mask` set if all BCD digits are in the range 0-9
+    pub const VALID_BCD: u16 = 1 << 6;
+    /// Bit of the `validity_mask` set if all values are within their valid ranges
+    pub const VALID_RANGES: u16 = 1 << 7;
+    /// The `validity_mask` of a frame passing all checks
+    pub const VALID_ALL: u16 = 0xff;
+
+    /// Return the positions and values of the bits which are fixed by the protocol: the start bit
+    /// is always 0 and the begin of the time information (bit 20) is always 1
+    pub const fn expected_fixed_bits() -> &'static [(usize, bool)] {
+        &[(0, false), (20, true)]
+    }
+
     /// Generate an empty value for the storage of the DCF77 data
     pub fn new(dcf77bits: u64) -> Self {
-        DCF77Time { 0: dcf77bits }
+        DCF77Time {
+            data: dcf77bits,
+            received: 59,
+        }
+    }
+
+    /// Generate a value from a frame of which only the first `count` bits were received. The
+    /// checked accessors refuse to verify fields whose bits, including the parity bit, are missing
+    pub fn with_received_bits(dcf77bits: u64, count: usize) -> Self {
+        DCF77Time {
+            data: dcf77bits,
+            received: count.min(59) as u8,
+        }
+    }
+
+    /// Return the raw bits of the frame, the start bit being the least significant one
+    pub fn bits(&self) -> u64 {
+        self.data
+    }
+
+    /// Return the number of bits which were received
+    pub fn received_bits(&self) -> usize {
+        self.received as usize
+    }
+
+    /// Check that the first `count` bits were received
+    fn require_bits(&self, count: u8) -> Result<(), DCF77Error> {
+        if self.received < count {
+            Err(DCF77Error::FrameIncomplete)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Generate a value from a capture which stores the 59 bits of a frame with inverted values,
+    /// i.e. 0 for a 200ms pulse and 1 for a 100ms pulse. This is unrelated to the signal level of
+    /// the receiver pin which is handled by the decoder
+    pub fn from_inverted(dcf77bits: u64) -> Self {
+        DCF77Time::new(!dcf77bits & ((1 << 59) - 1))
     }
 
     /// Validate the correct value of the start bit
-    pub fn validate_start(&self) -> Result<(), ()> {
-        if (self.0 & (1 << 0)) != 0 {
-            Err(())
+    pub fn validate_start(&self) -> Result<(), DCF77Error> {
+        self.require_bits(1)?;
+        if (self.data & (1 << 0)) != 0 {
+            Err(DCF77Error::StartBitSet)
         } else {
             Ok(())
         }
@@ -28,7 +153,7 @@ impl DCF77Time {
 
     /// Return whether summer time is signalled (without verifying the information)
     pub fn cest_unchecked(&self) -> bool {
-        if (self.0 & (1 << 17)) != 0 {
+        if (self.data & (1 << 17)) != 0 {
             return true;
         }
 
@@ -36,44 +161,76 @@ impl DCF77Time {
     }
 
     /// Return whether summer time is signalled with verification of the counter bit
-    pub fn cest(&self) -> Result<bool, ()> {
+    pub fn cest(&self) -> Result<bool, DCF77Error> {
+        self.require_bits(19)?;
         let cest = self.cest_unchecked();
 
-        if ((self.0 & (1 << 18)) != 0) == cest {
-            Err(())
+        if ((self.data & (1 << 18)) != 0) == cest {
+            Err(DCF77Error::TimezoneInconsistent)
         } else {
             Ok(cest)
         }
     }
 
+    /// Return the offset of the signalled time zone from UTC in minutes, i.e. 120 for CEST and 60
+    /// for CET, with verification of the counter bit like `cest`
+    pub fn utc_offset_minutes(&self) -> Result<i16, DCF77Error> {
+        Ok(if self.cest()? { 120 } else { 60 })
+    }
+
+    /// Return the encrypted weather and civil warning data of bits 1 to 14 packed into the lower
+    /// 14 bits, bit 1 being the least significant one, e.g. to forward them to an external decoder
+    pub fn weather_bits(&self) -> u16 {
+        ((self.data >> 1) & 0x3fff) as u16
+    }
+
+    /// Return whether the signal is transmitted from the backup antenna or under otherwise abnormal
+    /// operating conditions, which may reduce its accuracy (bit 15, without verifying the
+    /// information since no parity covers it)
+    pub fn abnormal_operation(&self) -> bool {
+        (self.data & (1 << 15)) != 0
+    }
+
+    /// Return whether a leap second at the end of the current hour is announced (bit 19, without
+    /// verifying the information), see `validate_leap_second`
+    pub fn leap_second_announced(&self) -> bool {
+        (self.data & (1 << 19)) != 0
+    }
+
+    /// Return whether a switch between CET and CEST at the end of the current hour is announced
+    /// (bit 16, without verifying the information since no parity covers it)
+    pub fn timezone_change_announced(&self) -> bool {
+        (self.data & (1 << 16)) != 0
+    }
+
     /// Return the current minutes of the hour (without verifying the information)
     pub fn minutes_unchecked(&self) -> u8 {
         let mut minutes = 0;
-        if (self.0 & (1 << 21)) != 0 {
+        if (self.data & (1 << 21)) != 0 {
             minutes += 1;
         }
 
-        if (self.0 & (1 << 22)) != 0 {
+        if (self.data & (1 << 22)) != 0 {
             minutes += 2;
         }
 
-        if (self.0 & (1 << 23)) != 0 {
+        if (self.data & (1 << 23)) != 0 {
             minutes += 4;
         }
 
-        if (self.0 & (1 << 24)) != 0 {
+        if (self.data & (1 << 24)) != 0 {
             minutes += 8;
         }
 
-        if (self.0 & (1 << 25)) != 0 {
+        if (self.data & (1 << 25)) != 0 {
             minutes += 10;
         }
 
-        if (self.0 & (1 << 26)) != 0 {
+        if (self.data & (1 << 26)) != 0 {
             minutes += 20;
         }
 
-        if (self.0 & (1 << 27)) != 0 {
+        if (self.data & (1 << 27)) != 0 {
             minutes += 40;
         }
 
@@ -81,43 +238,44 @@ impl DCF77Time {
     }
 
     /// Return the current minutes of the hour and verify parity and value < 60
-    pub fn minutes(&self) -> Result<u8, ()> {
+    pub fn minutes(&self) -> Result<u8, DCF77Error> {
+        self.require_bits(29)?;
         let mut parity = false;
-        if (self.0 & (1 << 21)) != 0 {
+        if (self.data & (1 << 21)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 22)) != 0 {
+        if (self.data & (1 << 22)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 23)) != 0 {
+        if (self.data & (1 << 23)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 24)) != 0 {
+        if (self.data & (1 << 24)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 25)) != 0 {
+        if (self.data & (1 << 25)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 26)) != 0 {
+        if (self.data & (1 << 26)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 27)) != 0 {
+        if (self.data & (1 << 27)) != 0 {
             parity ^= true;
         }
 
         let minutes = self.minutes_unchecked();
         if minutes > 59 {
-            return Err(());
+            return Err(DCF77Error::ValueOutOfRange(FieldKind::Minute));
         }
 
-        if ((self.0 & (1 << 28)) != 0) != parity {
-            Err(())
+        if ((self.data & (1 << 28)) != 0) != parity {
+            Err(DCF77Error::ParityMismatch)
         } else {
             Ok(minutes)
         }
@@ -126,27 +284,27 @@ impl DCF77Time {
     /// Return the current hours of the day (without verifying the information)
     pub fn hours_unchecked(&self) -> u8 {
         let mut hours = 0;
-        if (self.0 & (1 << 29)) != 0 {
+        if (self.data & (1 << 29)) != 0 {
             hours += 1;
         }
 
-        if (self.0 & (1 << 30)) != 0 {
+        if (self.data & (1 << 30)) != 0 {
             hours += 2;
         }
 
-        if (self.0 & (1 << 31)) != 0 {
+        if (self.data & (1 << 31)) != 0 {
             hours += 4;
         }
 
-        if (self.0 & (1 << 32)) != 0 {
+        if (self.data & (1 << 32)) != 0 {
             hours += 8;
         }
 
-        if (self.0 & (1 << 33)) != 0 {
+        if (self.data & (1 << 33)) != 0 {
             hours += 10;
         }
 
-        if (self.0 & (1 << 34)) != 0 {
+        if (self.data & (1 << 34)) != 0 {
             hours += 20;
         }
 
@@ -154,39 +312,40 @@ impl DCF77Time {
     }
 
     /// Return the current hours of the day and verify parity and value < 23
-    pub fn hours(&self) -> Result<u8, ()> {
+    pub fn hours(&self) -> Result<u8, DCF77Error> {
+        self.require_bits(36)?;
         let mut parity = false;
-        if (self.0 & (1 << 29)) != 0 {
+        if (self.data & (1 << 29)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 30)) != 0 {
+        if (self.data & (1 << 30)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 31)) != 0 {
+        if (self.data & (1 << 31)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 32)) != 0 {
+        if (self.data & (1 << 32)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 33)) != 0 {
+        if (self.data & (1 << 33)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 34)) != 0 {
+        if (self.data & (1 << 34)) != 0 {
             parity ^= true;
         }
 
         let hours = self.hours_unchecked();
         if hours > 23 {
-            return Err(());
+            return Err(DCF77Error::ValueOutOfRange(FieldKind::Hour));
         }
 
-        if ((self.0 & (1 << 35)) != 0) != parity {
-            Err(())
+        if ((self.data & (1 << 35)) != 0) != parity {
+            Err(DCF77Error::ParityMismatch)
         } else {
             Ok(hours)
         }
@@ -195,27 +354,27 @@ impl DCF77Time {
     /// Return the current day of month (without verifying the information)
     pub fn day_unchecked(&self) -> u8 {
         let mut day = 0;
-        if (self.0 & (1 << 36)) != 0 {
+        if (self.data & (1 << 36)) != 0 {
             day += 1;
         }
 
-        if (self.0 & (1 << 37)) != 0 {
+        if (self.data & (1 << 37)) != 0 {
             day += 2;
         }
 
-        if (self.0 & (1 << 38)) != 0 {
+        if (self.data & (1 << 38)) != 0 {
             day += 4;
         }
 
-        if (self.0 & (1 << 39)) != 0 {
+        if (self.data & (1 << 39)) != 0 {
             day += 8;
         }
 
-        if (self.0 & (1 << 40)) != 0 {
+        if (self.data & (1 << 40)) != 0 {
             day += 10;
         }
 
-        if (self.0 & (1 << 41)) != 0 {
+        if (self.data & (1 << 41)) != 0 {
             day += 20;
         }
 
@@ -223,190 +382,229 @@ impl DCF77Time {
     }
 
     /// Return the current day of month and do a basic value check
-    pub fn day(&self) -> Result<u8, ()> {
+    pub fn day(&self) -> Result<u8, DCF77Error> {
         let day = self.day_unchecked();
         if day > 31 {
-            Err(())
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Day))
         } else {
             Ok(day)
         }
     }
 
     /// Return the current day of the week (without verifying the information)
-    /// 0 meaning Monday
+    /// 1 meaning Monday
     pub fn weekday_unchecked(&self) -> u8 {
         let mut weekday = 0;
-        if (self.0 & (1 << 42)) != 0 {
+        if (self.data & (1 << 42)) != 0 {
             weekday += 1;
         }
 
-        if (self.0 & (1 << 43)) != 0 {
+        if (self.data & (1 << 43)) != 0 {
             weekday += 2;
         }
 
-        if (self.0 & (1 << 44)) != 0 {
+        if (self.data & (1 << 44)) != 0 {
             weekday += 4;
         }
         weekday
     }
 
+    /// Return the current day of the week, 1 meaning Monday, and verify the date parity and value
+    /// in 1..=7
+    pub fn weekday(&self) -> Result<u8, DCF77Error> {
+        self.validate_date_parity()?;
+
+        let weekday = self.weekday_unchecked();
+        if (1..=7).contains(&weekday) {
+            Ok(weekday)
+        } else {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Weekday))
+        }
+    }
+
     /// Return the current month of the year (without verifying the information)
     pub fn month_unchecked(&self) -> u8 {
         let mut month = 0;
-        if (self.0 & (1 << 45)) != 0 {
+        if (self.data & (1 << 45)) != 0 {
             month += 1;
         }
 
-        if (self.0 & (1 << 46)) != 0 {
+        if (self.data & (1 << 46)) != 0 {
             month += 2;
         }
 
-        if (self.0 & (1 << 47)) != 0 {
+        if (self.data & (1 << 47)) != 0 {
             month += 4;
         }
 
-        if (self.0 & (1 << 48)) != 0 {
+        if (self.data & (1 << 48)) != 0 {
             month += 8;
         }
 
-        if (self.0 & (1 << 49)) != 0 {
+        if (self.data & (1 << 49)) != 0 {
             month += 10;
         }
 
         month
     }
 
+    /// Return the current month of the year and verify the date parity and value in 1..=12
+    pub fn month(&self) -> Result<u8, DCF77Error> {
+        self.validate_date_parity()?;
+
+        let month = self.month_unchecked();
+        if (1..=12).contains(&month) {
+            Ok(month)
+        } else {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Month))
+        }
+    }
+
+    /// Verify the date parity (bit 58) which covers day, weekday, month and year
+    fn validate_date_parity(&self) -> Result<(), DCF77Error> {
+        self.require_bits(59)?;
+
+        if self.calculate_parity(36, 58) != ((self.data & (1 << 58)) != 0) {
+            Err(DCF77Error::ParityMismatch)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Return the current year (without verifying the information)
     pub fn year_unchecked(&self) -> u16 {
         let mut year = 2000;
-        if (self.0 & (1 << 50)) != 0 {
+        if (self.data & (1 << 50)) != 0 {
             year += 1;
         }
 
-        if (self.0 & (1 << 51)) != 0 {
+        if (self.data & (1 << 51)) != 0 {
             year += 2;
         }
 
-        if (self.0 & (1 << 52)) != 0 {
+        if (self.data & (1 << 52)) != 0 {
             year += 4;
         }
 
-        if (self.0 & (1 << 53)) != 0 {
+        if (self.data & (1 << 53)) != 0 {
             year += 8;
         }
 
-        if (self.0 & (1 << 54)) != 0 {
+        if (self.data & (1 << 54)) != 0 {
             year += 10;
         }
 
-        if (self.0 & (1 << 55)) != 0 {
+        if (self.data & (1 << 55)) != 0 {
             year += 20;
         }
 
-        if (self.0 & (1 << 56)) != 0 {
+        if (self.data & (1 << 56)) != 0 {
             year += 40;
         }
 
-        if (self.0 & (1 << 57)) != 0 {
+        if (self.data & (1 << 57)) != 0 {
             year += 80;
         }
 
         year
     }
 
-    /// Return a tuple of (year, month, day, weekday) if it passes a parity check
-    pub fn date(&self) -> Result<(u16, u8, u8, u8), ()> {
+    /// Return a tuple of (year, month, day, weekday) if it passes a parity check and the day
+    /// doesn't exceed the length of the month, taking leap years into account. The weekday isn't
+    /// compared to the date, see `validate_weekday_consistency` for that
+    pub fn date(&self) -> Result<(u16, u8, u8, u8), DCF77Error> {
+        self.require_bits(59)?;
         let mut parity = false;
-        if (self.0 & (1 << 36)) != 0 {
+        if (self.data & (1 << 36)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 37)) != 0 {
+        if (self.data & (1 << 37)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 38)) != 0 {
+        if (self.data & (1 << 38)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 39)) != 0 {
+        if (self.data & (1 << 39)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 40)) != 0 {
+        if (self.data & (1 << 40)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 41)) != 0 {
+        if (self.data & (1 << 41)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 42)) != 0 {
+        if (self.data & (1 << 42)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 43)) != 0 {
+        if (self.data & (1 << 43)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 44)) != 0 {
+        if (self.data & (1 << 44)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 45)) != 0 {
+        if (self.data & (1 << 45)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 46)) != 0 {
+        if (self.data & (1 << 46)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 47)) != 0 {
+        if (self.data & (1 << 47)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 48)) != 0 {
+        if (self.data & (1 << 48)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 49)) != 0 {
+        if (self.data & (1 << 49)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 50)) != 0 {
+        if (self.data & (1 << 50)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 51)) != 0 {
+        if (self.data & (1 << 51)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 52)) != 0 {
+        if (self.data & (1 << 52)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 53)) != 0 {
+        if (self.data & (1 << 53)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 54)) != 0 {
+        if (self.data & (1 << 54)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 55)) != 0 {
+        if (self.data & (1 << 55)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 56)) != 0 {
+        if (self.data & (1 << 56)) != 0 {
             parity ^= true;
         }
 
-        if (self.0 & (1 << 57)) != 0 {
+        if (self.data & (1 << 57)) != 0 {
             parity ^= true;
         }
 
-        if ((self.0 & (1 << 58)) != 0) != parity {
-            return Err(());
+        if ((self.data & (1 << 58)) != 0) != parity {
+            return Err(DCF77Error::ParityMismatch);
         }
 
         let year = self.year_unchecked();
@@ -414,42 +612,759 @@ impl DCF77Time {
         let day = self.day_unchecked();
         let weekday = self.weekday_unchecked();
 
-        if year > 2100 || month > 12 || day > 31 || weekday > 7 {
-            Err(())
+        if year > 2100 {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Year))
+        } else if month > 12 {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Month))
+        } else if day > days_in_month(year, month) {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Day))
+        } else if weekday > 7 {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Weekday))
         } else {
             Ok((year, month, day, weekday))
         }
     }
+
+    /// Return the fully decoded date/time if the start bit, all parities and value ranges are
+    /// correct. The checks run in the order of `validate_start`, `minutes`, `hours`, `cest` and
+    /// `date`, the first failing one determines the error
+    pub fn decode(&self) -> Result<DateTime, DCF77Error> {
+        self.validate_start()?;
+        let minute = self.minutes()?;
+        let hour = self.hours()?;
+        let cest = self.cest()?;
+        let (year, month, day, weekday) = self.date()?;
+
+        Ok(DateTime {
+            year,
+            month,
+            day,
+            weekday,
+            hour,
+            minute,
+            cest,
+        })
+    }
+
+    /// Return the same result as `decode`, optimized for the common case of a valid frame: the
+    /// cheap framing and range checks are done first and the parities are only calculated if
+    /// those pass. Any failing frame is handed over to `decode` to determine the exact error
+    pub fn decode_fast(&self) -> Result<DateTime, DCF77Error> {
+        let cest = self.cest_unchecked();
+        let framing = self.received == 59
+            && (self.data & 1) == 0
+            && (self.data & (1 << 18) != 0) != cest
+            && (self.data & (1 << 20)) != 0;
+
+        let minute = self.minutes_unchecked();
+        let hour = self.hours_unchecked();
+        let year = self.year_unchecked();
+        let month = self.month_unchecked();
+        let day = self.day_unchecked();
+        let weekday = self.weekday_unchecked();
+        let ranges = minute <= 59
+            && hour <= 23
+            && year <= 2100
+            && month <= 12
+            && day <= days_in_month(year, month)
+            && weekday <= 7;
+
+        if framing
+            && ranges
+            && self.calculate_parity(21, 28) == ((self.data & (1 << 28)) != 0)
+            && self.calculate_parity(29, 35) == ((self.data & (1 << 35)) != 0)
+            && self.calculate_parity(36, 58) == ((self.data & (1 << 58)) != 0)
+        {
+            Ok(DateTime {
+                year,
+                month,
+                day,
+                weekday,
+                hour,
+                minute,
+                cest,
+            })
+        } else {
+            self.decode()
+        }
+    }
+
+    /// Return the unchecked minutes of the hour together with whether parity and range are valid
+    pub fn minute_checked_pair(&self) -> (u8, bool) {
+        (self.minutes_unchecked(), self.minutes().is_ok())
+    }
+
+    /// Return the unchecked hours of the day together with whether parity and range are valid
+    pub fn hour_checked_pair(&self) -> (u8, bool) {
+        (self.hours_unchecked(), self.hours().is_ok())
+    }
+
+    /// Return the unchecked minutes of the hour together with whether only the parity is valid,
+    /// so a display can still show a plausible value marked as unverified
+    pub fn minutes_lenient(&self) -> (u8, bool) {
+        (self.minutes_unchecked(), !self.calculate_parity(21, 29))
+    }
+
+    /// Return the unchecked hours of the day together with whether only the parity is valid
+    pub fn hours_lenient(&self) -> (u8, bool) {
+        (self.hours_unchecked(), !self.calculate_parity(29, 36))
+    }
+
+    /// Return the unchecked tuple of (year, month, day, weekday) together with whether parity and
+    /// ranges are valid
+    pub fn date_checked_pair(&self) -> ((u16, u8, u8, u8), bool) {
+        let date = (
+            self.year_unchecked(),
+            self.month_unchecked(),
+            self.day_unchecked(),
+            self.weekday_unchecked(),
+        );
+
+        (date, self.date().is_ok())
+    }
+
+    /// Return all fields validated independently of each other, so e.g. the time can still be
+    /// displayed if the date failed validation. More forgiving than `decode` for display purposes
+    pub fn decode_partial(&self) -> PartialDecode {
+        PartialDecode {
+            start: self.validate_start(),
+            cest: self.cest(),
+            minute: self.minutes(),
+            hour: self.hours(),
+            date: self.date(),
+        }
+    }
+
+    /// Return every field with its own validation result, e.g. to highlight the failing fields of a
+    /// frame in a diagnostic display. The fields are checked like by `decode`, except that all
+    /// date fields are reported instead of only the first one out of range
+    pub fn decode_verbose(&self) -> DecodeReport {
+        let parity = if self.received < 59 {
+            Err(DCF77Error::FrameIncomplete)
+        } else if !self.date_parity_ok() {
+            Err(DCF77Error::ParityMismatch)
+        } else {
+            Ok(())
+        };
+        let year = self.year_unchecked();
+        let month = self.month_unchecked();
+        let day = self.day_unchecked();
+        let weekday = self.weekday_unchecked();
+        let check = |valid: bool, field: FieldKind| {
+            parity.and(if valid {
+                Ok(())
+            } else {
+                Err(DCF77Error::ValueOutOfRange(field))
+            })
+        };
+
+        DecodeReport {
+            minute: self.minutes(),
+            hour: self.hours(),
+            day: check(day <= days_in_month(year, month), FieldKind::Day).map(|_| day),
+            month: check(month <= 12, FieldKind::Month).map(|_| month),
+            weekday: check(weekday <= 7, FieldKind::Weekday).map(|_| weekday),
+            year: check(year <= 2100, FieldKind::Year).map(|_| year),
+            cest: self.cest(),
+        }
+    }
+
+    /// Return the best-effort date/time together with the results of all validation checks and
+    /// whether the frame can be trusted, i.e. decodes and passes all checks of `validity_mask`
+    pub fn interpret(&self) -> Interpretation {
+        let validity = self.validity_mask();
+
+        Interpretation {
+            time: DateTime {
+                year: self.year_unchecked(),
+                month: self.month_unchecked(),
+                day: self.day_unchecked(),
+                weekday: self.weekday_unchecked(),
+                hour: self.hours_unchecked(),
+                minute: self.minutes_unchecked(),
+                cest: self.cest_unchecked(),
+            },
+            fields: self.decode_partial(),
+            validity,
+            is_trustworthy: validity == Self::VALID_ALL && self.decode().is_ok(),
+        }
+    }
+
+    /// Return whether the verified hours and minutes match the given alarm time, expressed as
+    /// minutes since midnight. An alarm time beyond the end of the day never matches
+    pub fn matches_alarm(&self, minute_of_day: u16) -> Result<bool, DCF77Error> {
+        let hours = self.hours()? as u16;
+        let minutes = self.minutes()? as u16;
+
+        Ok(hours * 60 + minutes == minute_of_day)
+    }
+
+    /// Return a mask of the bits which changed compared to the previous frame. Between two
+    /// consecutive minutes usually only the minute bits and their parity change
+    pub fn diff(&self, prev: &DCF77Time) -> u64 {
+        self.data ^ prev.data
+    }
+
+    /// Return whether both frames decode and this one is exactly one minute after `previous`.
+    /// Both are compared in UTC, so hour, day, month and year rollovers as well as the summer time
+    /// changes, e.g. 01:59 CET followed by 03:00 CEST, are plausible
+    pub fn is_plausible_successor(&self, previous: &DCF77Time) -> bool {
+        match (previous.decode(), self.decode()) {
+            (Ok(previous), Ok(current)) => current.utc_minutes() - previous.utc_minutes() == 1,
+            _ => false,
+        }
+    }
+
+    /// Validate that the transmitted weekday matches the weekday calculated from the verified
+    /// calendar date
+    pub fn validate_weekday_consistency(&self) -> Result<(), DCF77Error> {
+        let (year, month, day, weekday) = self.date()?;
+        let days = days_from_civil(year as i32, month, day);
+
+        if (days + 3).rem_euclid(7) + 1 != weekday as i32 {
+            Err(DCF77Error::WeekdayInconsistent)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Return whether the transmitted weekday matches the weekday calculated from the verified
+    /// calendar date, see `validate_weekday_consistency`
+    pub fn weekday_consistent(&self) -> Result<bool, DCF77Error> {
+        match self.validate_weekday_consistency() {
+            Ok(()) => Ok(true),
+            Err(DCF77Error::WeekdayInconsistent) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Return the number of seconds since 1970-01-01 00:00 UTC of the decoded date/time, with the
+    /// CET/CEST offset subtracted. The local hour from 02:00 to 03:00 occurring twice when summer
+    /// time ends is unambiguous since the transmitted summer time flag tells both apart: the first
+    /// pass is sent as CEST and the repeated one as CET, so the timestamps keep increasing
+    pub fn unix_timestamp(&self) -> Result<i64, DCF77Error> {
+        let time = self.decode()?;
+
+        if time.month == 0 {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Month))
+        } else if time.day == 0 {
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Day))
+        } else {
+            Ok(time.utc_minutes() * 60)
+        }
+    }
+
+    /// Return the fully decoded date/time like `decode`, correcting a single flipped bit if exactly
+    /// one of the minute, hour and date parities fails: every bit of that field including its
+    /// parity bit is flipped in turn and the result is accepted if exactly one of the candidates
+    /// passes all checks of `decode` and `validate_weekday_consistency`. Otherwise the error of
+    /// `decode` is returned.
+    ///
+    /// Since flipping any bit of the field restores its parity, a correction only succeeds if the
+    /// range and weekday checks rule out all other candidates
+    pub fn decode_with_correction(&self) -> Result<DateTime, DCF77Error> {
+        let result = self.decode();
+        if result.is_ok() {
+            return result;
+        }
+
+        let fields = [
+            (self.minute_parity_ok(), 21, 29),
+            (self.hour_parity_ok(), 29, 36),
+            (self.date_parity_ok(), 36, 59),
+        ];
+        let mut failing = fields.iter().filter(|field| !field.0);
+        let (start, end) = match (failing.next(), failing.next()) {
+            (Some(&(_, start, end)), None) => (start, end),
+            _ => return result,
+        };
+
+        let mut corrected = None;
+        for bit in start..end {
+            let candidate = DCF77Time {
+                data: self.data ^ (1 << bit),
+                ..*self
+            };
+            let consistent = candidate.validate_weekday_consistency().is_ok();
+            if let (Ok(time), true) = (candidate.decode(), consistent) {
+                if corrected.is_some() {
+                    return result;
+                }
+                corrected = Some(time);
+            }
+        }
+
+        corrected.map_or(result, Ok)
+    }
+
+    /// Return the fully decoded date/time like `decode`, with the year interpreted in the century
+    /// and the additional calendar and weekday checks selected by `opts`
+    pub fn decode_with_options(&self, opts: DecodeOptions) -> Result<DateTime, DCF77Error> {
+        let mut time = self.decode()?;
+        time.year = (time.year - 2000)
+            .checked_add(opts.century)
+            .ok_or(DCF77Error::ValueOutOfRange(FieldKind::Year))?;
+
+        if opts.strict {
+            if time.month == 0 {
+                return Err(DCF77Error::ValueOutOfRange(FieldKind::Month));
+            }
+
+            if time.day == 0 || time.day > days_in_month(time.year, time.month) {
+                return Err(DCF77Error::ValueOutOfRange(FieldKind::Day));
+            }
+
+            if time.weekday == 0 {
+                return Err(DCF77Error::ValueOutOfRange(FieldKind::Weekday));
+            }
+        }
+
+        if opts.check_weekday {
+            let days = days_from_civil(time.year as i32, time.month, time.day);
+            if (days + 3).rem_euclid(7) + 1 != time.weekday as i32 {
+                return Err(DCF77Error::WeekdayInconsistent);
+            }
+        }
+
+        Ok(time)
+    }
+
+    /// Validate that the leap second announcement (bit 19) is only set during the hour before a
+    /// leap second may be inserted, i.e. at the end of the hours before January 1st or July 1st
+    /// 00:00 UTC, which are transmitted as 00:01 to 01:00 CET or 01:01 to 02:00 CEST
+    pub fn validate_leap_second(&self) -> Result<(), DCF77Error> {
+        if (self.data & (1 << 19)) == 0 {
+            return Ok(());
+        }
+
+        let time = self.decode()?;
+        let minute_of_day = time.hour as u16 * 60 + time.minute as u16;
+        let first = if time.cest { 61 } else { 1 };
+
+        if time.day == 1
+            && (time.month == 1 || time.month == 7)
+            && (first..=first + 59).contains(&minute_of_day)
+        {
+            Ok(())
+        } else {
+            Err(DCF77Error::LeapSecondInconsistent)
+        }
+    }
+
+    /// Return the Julian Day Number of the verified date, i.e. the number of days since noon of
+    /// January 1st 4713 BC in the proleptic Julian calendar, as a building block for astronomical
+    /// calculations like sunrise and sunset
+    pub fn julian_day(&self) -> Result<u32, DCF77Error> {
+        let (year, month, day, _) = self.date()?;
+
+        let a = (14 - month as u32) / 12;
+        let y = year as u32 + 4800 - a;
+        let m = month as u32 + 12 * a - 3;
+
+        Ok(day as u32 + (153 * m + 2) / 5 + 365 * y + y / 4 - y / 100 + y / 400 - 32045)
+    }
+
+    /// Return the verified date in ordinal form as (year, day of the year), January 1st being day
+    /// 1 and December 31st being day 365 or 366 in leap years
+    pub fn ordinal_date(&self) -> Result<(u16, u16), DCF77Error> {
+        let (year, month, day, _) = self.date()?;
+
+        if month == 0 {
+            return Err(DCF77Error::ValueOutOfRange(FieldKind::Month));
+        }
+
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(DCF77Error::ValueOutOfRange(FieldKind::Day));
+        }
+
+        let ordinal = days_from_civil(year as i32, month, day) - days_from_civil(year as i32, 1, 1);
+
+        Ok((year, ordinal as u16 + 1))
+    }
+
+    /// Return whether the minute bits were received and match their parity bit (bit 28)
+    pub fn minute_parity_ok(&self) -> bool {
+        self.received >= 29 && !self.calculate_parity(21, 29)
+    }
+
+    /// Return whether the hour bits were received and match their parity bit (bit 35)
+    pub fn hour_parity_ok(&self) -> bool {
+        self.received >= 36 && !self.calculate_parity(29, 36)
+    }
+
+    /// Return whether the date bits were received and match their parity bit (bit 58)
+    pub fn date_parity_ok(&self) -> bool {
+        self.received >= 59 && !self.calculate_parity(36, 59)
+    }
+
+    /// Return whether all three parity checks pass, see `minute_parity_ok`, `hour_parity_ok` and
+    /// `date_parity_ok`
+    pub fn all_parities_ok(&self) -> bool {
+        self.minute_parity_ok() && self.hour_parity_ok() && self.date_parity_ok()
+    }
+
+    /// Return the results of all validation checks as a bitmask of the `VALID_*` constants, a
+    /// set bit meaning the check passed. Cheap to log or transmit for telemetry purposes
+    pub fn validity_mask(&self) -> u16 {
+        let mut mask = 0;
+
+        if self.validate_start().is_ok() {
+            mask |= Self::VALID_START;
+        }
+
+        if (self.data & (1 << 20)) != 0 {
+            mask |= Self::VALID_FRAMING;
+        }
+
+        if self.minute_parity_ok() {
+            mask |= Self::VALID_MINUTE_PARITY;
+        }
+
+        if self.hour_parity_ok() {
+            mask |= Self::VALID_HOUR_PARITY;
+        }
+
+        if self.date_parity_ok() {
+            mask |= Self::VALID_DATE_PARITY;
+        }
+
+        let year = self.year_unchecked();
+        let month = self.month_unchecked();
+        let day = self.day_unchecked();
+        let weekday = self.weekday_unchecked();
+
+        let ranges = self.minutes_unchecked() <= 59
+            && self.hours_unchecked() <= 23
+            && (1..=31).contains(&day)
+            && (1..=12).contains(&month)
+            && (1..=7).contains(&weekday);
+
+        if ranges {
+            mask |= Self::VALID_RANGES;
+
+            let days = days_from_civil(year as i32, month, day);
+            if (days + 3).rem_euclid(7) + 1 == weekday as i32 {
+                mask |= Self::VALID_WEEKDAY;
+            }
+        }
+
+        // Units of minutes, hours, day, month and year as well as tens of year
+        let bcd = [21, 29, 36, 45, 50, 54]
+            .iter()
+            .all(|&start| (self.data >> start) & 0xf <= 9);
+
+        if bcd {
+            mask |= Self::VALID_BCD;
+        }
+
+        mask
+    }
+
+    /// Return `length` raw bits starting at bit `start`, the first bit being the least significant
+    /// one of the result. Meant for probing bits carrying auxiliary data, e.g. the civil warning
+    /// bits which some sources describe as carrying an approximate UT1-UTC (DUT1) correction.
+    /// Returns `None` if the range exceeds the 59 bits of a frame
+    pub fn aux_bits(&self, start: usize, length: usize) -> Option<u64> {
+        if length == 0 || start.checked_add(length).is_none_or(|end| end > 59) {
+            return None;
+        }
+
+        Some((self.data >> start) & ((1 << length) - 1))
+    }
+
+    /// Return the value of the bit at the given position of the frame, or `None` if the position
+    /// is beyond the 59 bits of a frame
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        if index < 59 {
+            Some((self.data & (1 << index)) != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over the 59 bits of the frame in the order of transmission, starting with
+    /// the start bit
+    pub fn iter_bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..59).map(move |bit| (self.data & (1 << bit)) != 0)
+    }
+
+    /// Return the number of set bits in the given field, not counting its parity bit. Together with
+    /// the parity bit this shows how a parity check came about
+    pub fn field_bit_count(&self, field: FieldKind) -> u8 {
+        let (start, end) = field.bits();
+        let mask = (1u64 << end) - (1u64 << start);
+
+        (self.data & mask).count_ones() as u8
+    }
+
+    /// Calculate the even parity over the bits from `start` up to (excluding) `end`
+    fn calculate_parity(&self, start: usize, end: usize) -> bool {
+        let mask = (1u64 << end) - (1u64 << start);
+
+        (self.data & mask).count_ones() & 1 == 1
+    }
+}
+
+/// The bits of a field of a frame from `start` up to (excluding) `end` in the order of
+/// transmission, with the last bit shown separately if it is the parity bit of the field
+struct FieldBits(u64, usize, usize, bool);
+
+impl fmt::Debug for FieldBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let FieldBits(data, start, end, parity) = *self;
+
+        for bit in start..end {
+            if parity && bit == end - 1 {
+                f.write_str(" p")?;
+            }
+            f.write_char(if (data & (1 << bit)) != 0 { '1' } else { '0' })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Show the bits of the frame grouped by field in the order of transmission, e.g. to spot a frame
+/// shifted by one bit
+impl fmt::Debug for DCF77Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DCF77Time")
+            .field("start", &FieldBits(self.data, 0, 1, false))
+            .field("weather", &FieldBits(self.data, 1, 15, false))
+            .field("flags", &FieldBits(self.data, 15, 20, false))
+            .field("time_start", &FieldBits(self.data, 20, 21, false))
+            .field("minute", &FieldBits(self.data, 21, 29, true))
+            .field("hour", &FieldBits(self.data, 29, 36, true))
+            .field("date", &FieldBits(self.data, 36, 59, true))
+            .field("received", &self.received)
+            .finish()
+    }
+}
+
+/// The interpretation of the received bits chosen by `SimpleDCF77Decoder::best_frame`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// The bits were received at their nominal positions
+    Nominal,
+    /// The first received bit was spurious, so all bits are moved one position down
+    DroppedFirst,
+    /// The start bit was missed, so all bits are moved one position up
+    InsertedStart,
+}
+
+/// The signal level a receiver module outputs while the carrier amplitude is reduced, i.e. during
+/// the pulse marking a bit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// The output is high during a pulse
+    ActiveHigh,
+    /// The output is low during a pulse
+    ActiveLow,
+}
+
+/// The minimum numbers of high samples the halves of the 200ms pulse window need to recognize a
+/// bit. A bit is a 1 if the second half reaches `one_min`, otherwise a 0 if the first half reaches
+/// `zero_min` and faulty if neither is met
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitThresholds {
+    /// The minimum number of high samples in the first 100ms of the pulse window for a 0 bit
+    pub zero_min: u16,
+    /// The minimum number of high samples in the second 100ms of the pulse window for a 1 bit
+    pub one_min: u16,
+}
+
+/// The fields of a DCF77 frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldKind {
+    /// The minutes (bits 21-27), protected by the parity bit 28
+    Minute,
+    /// The hours (bits 29-34), protected by the parity bit 35
+    Hour,
+    /// The day of the month (bits 36-41)
+    Day,
+    /// The day of the week (bits 42-44)
+    Weekday,
+    /// The month (bits 45-49)
+    Month,
+    /// The year within the century (bits 50-57)
+    Year,
+    /// The complete date (bits 36-57), protected by the parity bit 58
+    Date,
+}
+
+impl FieldKind {
+    /// Return the range of bits of the field as start and (excluding) end
+    fn bits(self) -> (usize, usize) {
+        match self {
+            FieldKind::Minute => (21, 28),
+            FieldKind::Hour => (29, 35),
+            FieldKind::Day => (36, 42),
+            FieldKind::Weekday => (42, 45),
+            FieldKind::Month => (45, 50),
+            FieldKind::Year => (50, 58),
+            FieldKind::Date => (36, 58),
+        }
+    }
 }
 
-enum SimpleDCF77DecoderState {
+/// The states of the `SimpleDCF77Decoder` state machine
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum SimpleDCF77DecoderState {
+    /// Waiting for the rising edge of the next pulse
     WaitingForPhase,
+    /// Sampling the 200ms window of a pulse
     PhaseFound,
+    /// A bit was received with the latest sample
     BitReceived,
+    /// The pulse couldn't be recognized as either bit value
     FaultyBit,
+    /// The minute marker ending a cycle was detected with the latest sample
     EndOfCycle,
+    /// Waiting for the remainder of the second after a received bit
     Idle,
 }
 
+/// The reasons for the `SimpleDCF77Decoder` to report a faulty bit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum FaultKind {
+    /// Neither half of the 200ms pulse window had enough high samples to recognize a bit
+    AmbiguousBit,
+    /// The signal stayed high far longer than a pulse after a received bit
+    NoiseAfterBit,
+    /// Too many high samples between two pulses, e.g. interference, restarted the search for the
+    /// next pulse
+    NoisySecond,
+    /// A 1 was received as start bit right after the minute marker
+    StartBitSet,
+    /// A gap in the signal ended the cycle after the wrong number of bits, e.g. a dropout
+    /// masquerading as minute marker, or didn't match the configured marker gap bounds
+    PhaseTimeout,
+}
+
 /// A structure for a simple timeslot based DCF77 decoder
 pub struct SimpleDCF77Decoder  {
-    scancount: u8,
-    lowcount: u8,
-    highcount: u8,
-    idlecount: u8,
+    periodms: u16,
+    markersamples: u16,
+    bitsamples: u16,
+    splitsamples: u16,
+    idlesamples: u16,
+    thresholds: BitThresholds,
+    noisesamples: u16,
+    longsamples: u16,
+    secondlength: u32,
+    scancount: u16,
+    lowcount: u16,
+    highcount: u16,
+    idlecount: u16,
     state: SimpleDCF77DecoderState,
     data: u64,
     datapos: usize,
+    confidence: u8,
+    confidencesum: u16,
+    frameconfidence: u8,
+    faultcount: u8,
+    cycleerrors: u8,
+    goodminutes: u16,
+    lastfault: Option<FaultKind>,
+    framecomplete: bool,
+    cyclelength: usize,
+    samplecount: u32,
+    alignmenterror: i16,
+    lastbittick: Option<u32>,
+    lastbituncertain: bool,
+    pulselength: u16,
+    minutestart: Option<u32>,
+    markerseen: bool,
+    dutyhigh: u16,
+    dutysamples: u16,
+    dutycycle: u8,
+    markerbounds: Option<(u16, u16)>,
+    markeranomaly: bool,
+    markerclosed: bool,
+    polarity: Polarity,
+    debounce: u8,
+    debouncecount: u8,
+    filteredlevel: bool,
+    #[cfg(feature = "event-log")]
+    events: EventLog,
+}
+
+impl Default for SimpleDCF77Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Take the frame of a decoder which just completed a minute, i.e. from the sample on which
+/// `end_of_cycle` returned true until the next bit is received or a fault occurs. At any other time
+/// the conversion fails with `DCF77Error::FrameIncomplete`
+impl<'a> TryFrom<&'a SimpleDCF77Decoder> for DCF77Time {
+    type Error = DCF77Error;
+
+    fn try_from(decoder: &'a SimpleDCF77Decoder) -> Result<Self, Self::Error> {
+        if decoder.framecomplete {
+            Ok(DCF77Time::new(decoder.raw_frame()))
+        } else {
+            Err(DCF77Error::FrameIncomplete)
+        }
+    }
 }
 
 /// The SimpleDCF77Decoder implements a simple state machine to decode a DCF77 signal from a fed-in
 /// readout of a GPIO pin connected to a DCF77 receiver. To use this, create the structure, set up
 /// the GPIO pin the receiver is connected to as an input and call the `read_bit` method every
-/// 10ms with a parameter value of `true` for a high signal level or `false` for a low signal level
+/// 10ms, or the period passed to `with_sample_period_ms`, with a parameter value of `true` for a
+/// high signal level or `false` for a low signal level. The phase is resynchronized with every
+/// pulse and the windows spanning a second or more follow the measured length of a second, which
+/// tolerates sample clocks running up to 10% too fast or too slow.
+///
+/// The decoder runs continuously without any intervention: after a faulty bit, a dropout or the
+/// end of a cycle it waits for the next pulse by itself and starts collecting the following minute,
+/// so nothing but `read_bit` has to be called and checking `end_of_cycle` may be skipped any time
 impl SimpleDCF77Decoder {
-    /// Create a new decoder state machine
-    pub fn new() -> Self {
+    /// Create a new decoder state machine expecting a sample every 10ms
+    pub const fn new() -> Self {
+        Self::with_sample_period_ms(10)
+    }
+
+    /// Create a new decoder state machine expecting a sample every `period_ms` milliseconds. All
+    /// timing thresholds are derived from the period, which is limited to the range of 1ms to
+    /// 50ms.
+    ///
+    /// Firmwares sampling at 50 Hz use a period of 20ms: the 200ms pulse window then only consists
+    /// of 10 samples with 5 each before and after the 100ms split between a 0 and a 1 bit, and a
+    /// bit is recognized as soon as at least 2 samples of a half are high.
+    ///
+    /// Since this is a `const fn`, a decoder in a `static` gets all thresholds calculated at
+    /// compile time, while `read_bit` only ever compares against the precalculated values
+    pub const fn with_sample_period_ms(period_ms: u16) -> Self {
+        let period = if period_ms < 1 {
+            1
+        } else if period_ms > 50 {
+            50
+        } else {
+            period_ms
+        };
+
         Self {
+            periodms: period,
+            markersamples: 1800 / period,
+            bitsamples: 200 / period,
+            splitsamples: 100 / period,
+            idlesamples: 900 / period,
+            thresholds: BitThresholds {
+                zero_min: 30 / period + 1,
+                one_min: 30 / period + 1,
+            },
+            noisesamples: 100 / period,
+            longsamples: 300 / period,
+            secondlength: (1000 / period) as u32 * 16,
             scancount: 0,
             lowcount: 0,
             highcount: 0,
@@ -457,75 +1372,554 @@ impl SimpleDCF77Decoder {
             state: SimpleDCF77DecoderState::WaitingForPhase,
             data: 0,
             datapos: 0,
+            confidence: 0,
+            confidencesum: 0,
+            frameconfidence: 0,
+            faultcount: 0,
+            cycleerrors: 0,
+            goodminutes: 0,
+            lastfault: None,
+            framecomplete: false,
+            cyclelength: 0,
+            samplecount: 0,
+            alignmenterror: 0,
+            lastbittick: None,
+            lastbituncertain: false,
+            pulselength: 0,
+            minutestart: None,
+            markerseen: false,
+            dutyhigh: 0,
+            dutysamples: 1000 / period,
+            dutycycle: 0,
+            markerbounds: None,
+            markeranomaly: false,
+            markerclosed: false,
+            polarity: Polarity::ActiveHigh,
+            debounce: 1,
+            debouncecount: 0,
+            filteredlevel: false,
+            #[cfg(feature = "event-log")]
+            events: EventLog::new(),
         }
     }
 
-    /// Return the raw data as `u64` value for decoding of the current date/time
-    pub fn raw_data(&self) -> u64 {
-        self.data
+    /// Return a builder to create a decoder state machine with several options differing from the
+    /// defaults of `new`
+    pub const fn builder() -> SimpleDCF77DecoderBuilder {
+        SimpleDCF77DecoderBuilder::new()
     }
 
-    /// Returns true as soon as an individual bit was received
-    pub fn bit_complete(&self) -> bool {
-        match self.state {
-            SimpleDCF77DecoderState::BitReceived => true,
-            _ => false,
-        }
+    /// Create a new decoder state machine expecting a sample every 10ms from a receiver with the
+    /// given output polarity
+    pub fn with_polarity(polarity: Polarity) -> Self {
+        let mut decoder = Self::new();
+        decoder.polarity = polarity;
+        decoder
     }
 
-    /// Returns true if the last bit couldn't be identified as high/low
-    pub fn bit_faulty(&self) -> bool {
-        match self.state {
-            SimpleDCF77DecoderState::FaultyBit => true,
-            _ => false,
-        }
+    /// Set the output polarity of the receiver. With `Polarity::ActiveLow` every sample passed to
+    /// `read_bit` is inverted before being fed to the state machine, so `true` can always be
+    /// passed for a high pin level
+    pub fn set_polarity(&mut self, polarity: Polarity) {
+        self.polarity = polarity;
     }
 
-    /// Returns true if the end of a 59s cycle was detected
-    pub fn end_of_cycle(&self) -> bool {
-        match self.state {
-            SimpleDCF77DecoderState::EndOfCycle => true,
-            _ => false,
-        }
+    /// Return the configured output polarity of the receiver
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
     }
 
-    /// Returns the value of the latest received bit. Mainly useful for live display of the
-    /// received bits
-    pub fn latest_bit(&self) -> bool {
-        (self.data & (1 << (self.datapos - 1))) != 0
+    /// Create a new decoder state machine expecting a sample every 10ms which only follows a level
+    /// change of the signal after `samples` consecutive samples, see `set_debounce`
+    pub fn with_debounce(samples: u8) -> Self {
+        let mut decoder = Self::new();
+        decoder.set_debounce(samples);
+        decoder
     }
 
-    /// Return the current position of the bit counter after the latest recognized end of a cycle
-    /// which is identical to the current second of the minute
+    /// Only follow a level change of the signal once `samples` consecutive samples show the new
+    /// level, so a single spurious sample of a glitch can't affect the recognized bits. Both edges
+    /// are delayed by `samples - 1` samples which keeps the pulse lengths intact. A value of 0 or
+    /// 1, the default, disables the filter
+    pub fn set_debounce(&mut self, samples: u8) {
+        self.debounce = samples.max(1);
+        self.debouncecount = 0;
+    }
+
+    /// Return the number of consecutive samples required for a level change
+    pub fn debounce(&self) -> u8 {
+        self.debounce
+    }
+
+    /// Create a new decoder state machine expecting a sample every 10ms which recognizes bits
+    /// according to the given thresholds
+    pub fn with_bit_thresholds(thresholds: BitThresholds) -> Self {
+        let mut decoder = Self::new();
+        decoder.thresholds = thresholds;
+        decoder
+    }
+
+    /// Set the minimum numbers of high samples to recognize a bit, e.g. lower ones to accept the
+    /// pulses of a noisy receiver. By default more than 30ms of a half have to be high
+    pub fn set_bit_thresholds(&mut self, thresholds: BitThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    /// Return the configured minimum numbers of high samples to recognize a bit
+    pub fn bit_thresholds(&self) -> BitThresholds {
+        self.thresholds
+    }
+
+    /// Return the sample period in ms the decoder expects `read_bit` to be called with
+    pub fn sample_period_ms(&self) -> u16 {
+        self.periodms
+    }
+
+    /// Return the raw data as `u64` value for decoding of the current date/time
+    pub fn raw_data(&self) -> u64 {
+        self.data
+    }
+
+    /// Return the raw data masked to exactly the 59 bits of a frame, so stale bits above bit 58
+    /// can't affect comparing or hashing frames
+    pub fn raw_frame(&self) -> u64 {
+        self.data & ((1 << 59) - 1)
+    }
+
+    /// Return the first interpretation of the received bits which decodes to a valid date/time
+    /// together with the chosen alignment. If the nominal alignment shows the signature of a phase
+    /// lock one second off, i.e. the start bit is set, the begin of time information (bit 20) is
+    /// cleared or the cycle didn't have 59 bits, this also tries the candidates where a spurious
+    /// bit was received before the start bit or the start bit was missed. Meant to be called once
+    /// `cycle_ended` returned true
+    pub fn best_frame(&self) -> Option<(DCF77Time, Alignment)> {
+        let mask = (1 << 59) - 1;
+        let length = self.cyclelength;
+        let candidates = [
+            (self.data & mask, length, Alignment::Nominal),
+            ((self.data >> 1) & mask, length.saturating_sub(1), Alignment::DroppedFirst),
+            ((self.data << 1) & mask, length + 1, Alignment::InsertedStart),
+        ];
+
+        let misframed = length != 59 || (self.data & 1) != 0 || (self.data & (1 << 20)) == 0;
+
+        candidates
+            .iter()
+            .take(if misframed { 3 } else { 1 })
+            .map(|&(bits, length, alignment)| {
+                (DCF77Time::with_received_bits(bits, length), alignment)
+            })
+            .find(|(frame, _)| frame.decode().is_ok())
+    }
+
+    /// Return the received frame, i.e. the completed one right after `cycle_ended` returned true
+    /// and the partially received one while the next cycle is being received
+    pub fn received_frame(&self) -> DCF77Time {
+        let length = if self.datapos == 0 {
+            self.cyclelength
+        } else {
+            self.datapos
+        };
+
+        DCF77Time::with_received_bits(self.raw_frame(), length)
+    }
+
+    /// Return the current state of the state machine. With marker gap bounds configured the end of
+    /// a cycle is reported together with the pulse starting the next minute, so `cycle_ended`
+    /// should be used to detect it
+    pub fn state(&self) -> SimpleDCF77DecoderState {
+        self.state
+    }
+
+    /// Returns true as soon as an individual bit was received
+    pub fn bit_complete(&self) -> bool {
+        matches!(self.state, SimpleDCF77DecoderState::BitReceived)
+    }
+
+    /// Returns true if the last bit couldn't be identified as high/low or was a set start bit
+    pub fn bit_faulty(&self) -> bool {
+        matches!(self.state, SimpleDCF77DecoderState::FaultyBit)
+    }
+
+    /// Returns the reason of the latest faulty bit, noisy second or discarded cycle, kept until
+    /// `clear_faults` or `reset` is called
+    pub fn last_fault(&self) -> Option<FaultKind> {
+        self.lastfault
+    }
+
+    /// Returns true if the end of a 59s cycle was detected, i.e. the minute marker was seen right
+    /// after exactly 59 bits were received, or 60 bits if a leap second was inserted. A gap after
+    /// fewer or more bits, e.g. a dropout in the middle of a minute, is only reported by
+    /// `cycle_ended`
+    pub fn end_of_cycle(&self) -> bool {
+        self.minute_marker_detected()
+    }
+
+    /// Returns true if the minute marker, the missing pulse of second 59, was detected with the
+    /// latest sample and coincides with having received 59 bits. In a minute with an announced
+    /// leap second the marker is the missing pulse of second 60 and follows a 0 bit as second 59
+    pub fn minute_marker_detected(&self) -> bool {
+        self.cycle_ended() && self.complete_minute()
+    }
+
+    /// Returns true if the latest completed cycle had 59 bits, or 60 bits including an inserted
+    /// leap second
+    fn complete_minute(&self) -> bool {
+        self.cyclelength == 59 || self.was_leap_minute()
+    }
+
+    /// Returns true if a leap second was inserted into the latest completed cycle, i.e. it had 60
+    /// bits, the leap second was announced and the additional second 59 was a 0 bit. Only
+    /// meaningful once `cycle_ended` returned true
+    pub fn was_leap_minute(&self) -> bool {
+        self.cyclelength == 60 && (self.data & (1 << 19)) != 0 && (self.data & (1 << 59)) == 0
+    }
+
+    /// Returns true if a gap ended the current cycle with the latest sample, regardless of the
+    /// number of bits received. Cycles of 58 or 60 bits can still be recovered with `best_frame`
+    pub fn cycle_ended(&self) -> bool {
+        self.markerclosed || matches!(self.state, SimpleDCF77DecoderState::EndOfCycle)
+    }
+
+    /// Require the gap between the pulses of second 58 and of second 0 of the next minute, which
+    /// nominally lasts 2000ms, to be between `min_ms` and `max_ms`. With bounds set, the end of a
+    /// cycle is reported with the pulse starting the next minute once the gap is known to be
+    /// within the bounds. Any gap between two pulses longer than 1500ms outside of the bounds,
+    /// e.g. a dropout masquerading as minute marker, is reported by `marker_anomaly` and discards
+    /// the current cycle. Without bounds, which is the default, the end of a cycle is reported as
+    /// soon as no pulse was seen for 1800ms
+    pub fn set_marker_gap_bounds_ms(&mut self, bounds: Option<(u16, u16)>) {
+        self.markerbounds = bounds.map(|(min, max)| {
+            let max = max.min(u16::MAX - 1);
+            (min.min(max) / self.periodms, max / self.periodms)
+        });
+        self.markeranomaly = false;
+    }
+
+    /// Returns true if the latest gap between two pulses was outside of the bounds configured
+    /// with `set_marker_gap_bounds_ms`
+    pub fn marker_anomaly(&self) -> bool {
+        self.markeranomaly
+    }
+
+    /// Returns the value of the latest received bit of the current cycle, or `None` if none was
+    /// received yet, i.e. after creation or a reset and while waiting for the first bit following a
+    /// minute marker. Mainly useful for live display of the received bits
+    pub fn latest_bit(&self) -> Option<bool> {
+        let datapos = self.datapos.checked_sub(1)?;
+
+        Some((self.data & (1 << datapos)) != 0)
+    }
+
+    /// Returns the confidence in percent of the latest received bit, i.e. how many samples of the
+    /// 200ms pulse window matched the ideal waveform of the recognized bit value. A faulty bit has
+    /// a confidence of 0
+    pub fn bit_confidence(&self) -> u8 {
+        self.confidence
+    }
+
+    /// Returns the average confidence in percent of all bits of the latest completed cycle. Only
+    /// meaningful once `cycle_ended` returned true
+    pub fn frame_confidence(&self) -> u8 {
+        self.frameconfidence
+    }
+
+    /// Returns the number of faulty bits in the latest completed cycle. Only meaningful once
+    /// `cycle_ended` returned true
+    pub fn last_cycle_error_count(&self) -> u8 {
+        self.cycleerrors
+    }
+
+    /// Returns the quality of the signal in percent during the latest completed cycle, i.e. the
+    /// share of the 59 bits of a frame which were received without a fault. A cycle cut short by
+    /// a dropout counts the missing bits as faulty
+    pub fn signal_quality(&self) -> u8 {
+        let clean = self.cyclelength.saturating_sub(self.cycleerrors as usize);
+
+        (clean * 100 / 59).min(100) as u8
+    }
+
+    /// Returns the number of consecutive minutes which were received completely without any
+    /// faulty bit and decoded successfully, e.g. to only trust the time after a few good minutes.
+    /// Drops to 0 as soon as a faulty bit is received or a minute fails
+    pub fn consecutive_good_minutes(&self) -> u16 {
+        self.goodminutes
+    }
+
+    /// Return the raw data of the latest minute completed by a minute marker exactly once, so it
+    /// doesn't have to be fetched with the very sample completing the minute. The frame is only
+    /// available until the next bit is received or a fault occurs, like `DCF77Time::try_from`
+    pub fn take_completed_frame(&mut self) -> Option<u64> {
+        if mem::replace(&mut self.framecomplete, false) {
+            Some(self.raw_frame())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of bits received in the latest completed cycle, which is 59 for a
+    /// complete frame
+    pub fn cycle_length(&self) -> usize {
+        self.cyclelength
+    }
+
+    /// Returns the offset in ms of the latest decoded second boundary, i.e. the begin of the
+    /// latest pulse, from the local second boundary which is derived from the number of samples
+    /// fed in since the creation of the decoder. A positive value means the DCF77 second starts
+    /// after the local second, this can be used to discipline a local oscillator
+    pub fn second_alignment_error_ms(&self) -> i16 {
+        self.alignmenterror
+    }
+
+    /// Return the number of milliseconds of the latest sample into the current minute, counted
+    /// from the pulse starting the first second after the latest minute marker. Returns 0 until a
+    /// minute marker was seen
+    pub fn now_millis_into_minute(&self) -> u32 {
+        match self.minutestart {
+            Some(start) => self
+                .samplecount
+                .wrapping_sub(start)
+                .wrapping_sub(1)
+                .wrapping_mul(self.periodms as u32),
+            None => 0,
+        }
+    }
+
+    /// Return the percentage of high samples during the one second window starting with the
+    /// latest completely received pulse. A healthy signal shows about 10% for a 0 bit and 20% for
+    /// a 1 bit, values far off indicate a problem with the reception
+    pub fn last_bit_duty_cycle(&self) -> u8 {
+        self.dutycycle
+    }
+
+    /// Returns the tick passed to `read_bit_ts` with the sample which completed the latest bit
+    pub fn last_bit_tick(&self) -> Option<u32> {
+        self.lastbittick
+    }
+
+    /// Returns true if the last bit of the latest completed cycle is uncertain, i.e. bit 58 was
+    /// faulty, barely recognized or not received at all because the cycle only had 58 bits. The
+    /// frame is still delivered so the caller can decide whether to trust the date parity
+    pub fn last_bit_uncertain(&self) -> bool {
+        self.lastbituncertain
+    }
+
+    /// Returns the name of the current internal state of the state machine, e.g. for assertions
+    /// in tests driving the decoder sample by sample
+    #[cfg(any(test, feature = "debug-state"))]
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            SimpleDCF77DecoderState::WaitingForPhase => "WaitingForPhase",
+            SimpleDCF77DecoderState::PhaseFound => "PhaseFound",
+            SimpleDCF77DecoderState::BitReceived => "BitReceived",
+            SimpleDCF77DecoderState::FaultyBit => "FaultyBit",
+            SimpleDCF77DecoderState::EndOfCycle => "EndOfCycle",
+            SimpleDCF77DecoderState::Idle => "Idle",
+        }
+    }
+
+    /// Return an iterator over the latest recorded decoder events from the oldest to the newest
+    /// one, keeping at most `EVENT_LOG_CAPACITY` events
+    #[cfg(feature = "event-log")]
+    pub fn events(&self) -> impl Iterator<Item = LoggedEvent> + '_ {
+        self.events.iter()
+    }
+
+    /// Forget all recorded decoder events
+    #[cfg(feature = "event-log")]
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    /// Return the current position of the bit counter after the latest recognized end of a cycle,
+    /// i.e. the number of bits received in the current cycle. Once a minute marker was seen this
+    /// is identical to the current second of the minute, the number of the next bit to receive
     pub fn seconds(&self) -> usize {
         self.datapos
     }
 
+    /// Return the number of bits still to be received until the current minute completes, which
+    /// is 59 minus `seconds`. In the minute before an announced leap second, i.e. once the
+    /// announcement and a minute value of 0 were received, one more bit is expected
+    pub fn bits_remaining(&self) -> usize {
+        let frame = DCF77Time::with_received_bits(self.raw_frame(), self.datapos);
+        let bits: usize = if frame.leap_second_announced() && frame.minutes() == Ok(0) {
+            60
+        } else {
+            59
+        };
+
+        bits.saturating_sub(self.datapos)
+    }
+
+    /// Return an iterator over the bits received so far in the current cycle in the order of
+    /// transmission, e.g. to show them on a status display
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.datapos).map(move |bit| (self.data & (1 << bit)) != 0)
+    }
+
+    /// Render the bits of the current cycle into `buf` as one character per second, `0` or `1` for
+    /// a received bit and `-` for one not received yet, with the fields separated by `|`, e.g.
+    /// `0|00000000000000|00010|1|1110110-|-------|-----------------------`. The whole timeline
+    /// takes 65 bytes, a shorter buffer only receives its beginning
+    pub fn render_timeline<'a>(&self, buf: &'a mut [u8]) -> &'a str {
+        let mut len = 0;
+        {
+            let mut push = |c: u8| {
+                if len < buf.len() {
+                    buf[len] = c;
+                    len += 1;
+                }
+            };
+
+            for bit in 0..59 {
+                if [1, 15, 20, 21, 29, 36].contains(&bit) {
+                    push(b'|');
+                }
+
+                push(if bit >= self.datapos {
+                    b'-'
+                } else if (self.data & (1 << bit)) != 0 {
+                    b'1'
+                } else {
+                    b'0'
+                });
+            }
+        }
+
+        core::str::from_utf8(&buf[..len]).unwrap_or("")
+    }
+
+    /// Return the progress of the current cycle in percent, e.g. for a progress bar during the
+    /// first synchronisation
+    pub fn progress_percent(&self) -> u8 {
+        if self.datapos >= 59 {
+            100
+        } else {
+            (self.datapos * 100 / 59) as u8
+        }
+    }
+
+    /// Drop the latest received bit, e.g. because a higher layer found it to be misclassified. The
+    /// bit counter is decremented and the bit cleared so the next received bit takes its place
+    pub fn rewind_one_bit(&mut self) {
+        if self.datapos > 0 {
+            self.datapos -= 1;
+            self.data &= !(1 << self.datapos);
+            self.confidencesum = self.confidencesum.saturating_sub(self.confidence as u16);
+            self.confidence = 0;
+        }
+    }
+
+    /// Clear the faulty bit state, the reason of the latest fault and the number of faulty bits of
+    /// the current cycle, e.g. after recovering from interference, while preserving the bits of
+    /// the frame received so far
+    pub fn clear_faults(&mut self) {
+        self.faultcount = 0;
+        self.lastfault = None;
+        if let SimpleDCF77DecoderState::FaultyBit = self.state {
+            self.state = SimpleDCF77DecoderState::WaitingForPhase;
+        }
+    }
+
+    /// Return the decoder to the state of a decoder created with `new`, e.g. after the receiver
+    /// lost the signal or was replugged
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Return the decoder to its initial state like `reset` while keeping the configured sample
+    /// period, polarity, debounce filter, bit thresholds and marker gap bounds
+    pub fn reset_preserving_period(&mut self) {
+        let mut decoder = Self::with_sample_period_ms(self.periodms);
+        decoder.polarity = self.polarity;
+        decoder.debounce = self.debounce;
+        decoder.thresholds = self.thresholds;
+        decoder.markerbounds = self.markerbounds;
+        *self = decoder;
+    }
+
+    /// Ingest the latest sample like `read_bit` together with a monotonic tick of the caller,
+    /// which is stored if the sample completes a bit and can be retrieved with `last_bit_tick`
+    pub fn read_bit_ts(&mut self, bit: bool, tick: u32) {
+        self.read_bit(bit);
+
+        if self.bit_complete() {
+            self.lastbittick = Some(tick);
+        }
+    }
+
     /// Ingest the latest sample of the GPIO input the DCF77 receiver is connected to judge the /
     /// current position and value of the DCF77 signal bitstream
     pub fn read_bit(&mut self, bit: bool) {
+        let bit = self.filter(bit != (self.polarity == Polarity::ActiveLow));
+
+        #[cfg(feature = "event-log")]
+        let previous = self.state;
+
+        self.pulselength = if bit { self.pulselength.saturating_add(1) } else { 0 };
+        self.markerclosed = false;
+
         self.state = match self.state {
             SimpleDCF77DecoderState::EndOfCycle | SimpleDCF77DecoderState::WaitingForPhase | SimpleDCF77DecoderState::FaultyBit => {
-                if bit {
+                if bit && self.pulselength <= self.longsamples {
+                    if let Some((min, max)) = self.markerbounds {
+                        if self.scancount > 1500 / self.periodms {
+                            if (min..=max).contains(&self.scancount) {
+                                self.complete_cycle();
+                                self.markeranomaly = false;
+                                self.markerclosed = true;
+                            } else {
+                                self.discard_cycle();
+                            }
+                        }
+                    }
+
+                    self.track_second_length();
+
+                    let samplespersecond = (1000 / self.periodms) as u32;
+                    let offset = (self.samplecount % samplespersecond) as i16;
+                    let offset = if offset > samplespersecond as i16 / 2 {
+                        offset - samplespersecond as i16
+                    } else {
+                        offset
+                    };
+                    self.alignmenterror = offset * self.periodms as i16;
+                    if self.datapos == 0 && self.markerseen {
+                        self.minutestart = Some(self.samplecount);
+                    }
                     self.lowcount = 1;
                     self.highcount = 0;
+                    self.idlecount = 0;
                     self.scancount = 0;
+                    self.dutyhigh = 0;
+                    self.dutysamples = 0;
                     SimpleDCF77DecoderState::PhaseFound
+                } else if bit {
+                    // A stuck high signal neither starts a new bit nor marks the end of a cycle
+                    SimpleDCF77DecoderState::WaitingForPhase
                 } else {
-                    if self.scancount > 180 {
-                        self.datapos = 0;
-                        self.scancount = 0;
+                    match self.markerbounds {
+                        Some((_, max)) => {
+                            if self.scancount == max + 1 {
+                                self.discard_cycle();
+                            }
+                            SimpleDCF77DecoderState::WaitingForPhase
+                        }
+                        None if self.scancount > self.markersamples => {
+                            self.complete_cycle();
+                            self.scancount = 0;
 
-                        SimpleDCF77DecoderState::EndOfCycle
-                    } else {
-                        SimpleDCF77DecoderState::WaitingForPhase
+                            SimpleDCF77DecoderState::EndOfCycle
+                        }
+                        None => SimpleDCF77DecoderState::WaitingForPhase,
                     }
                 }
             }
             SimpleDCF77DecoderState::PhaseFound => {
-                if self.scancount < 20 {
+                if self.scancount < self.bitsamples {
                     if bit {
-                        if self.scancount < 10 {
+                        if self.scancount < self.splitsamples {
                             self.lowcount += 1;
                         } else {
                             self.highcount += 1;
@@ -533,29 +1927,68 @@ impl SimpleDCF77Decoder {
                     }
                     SimpleDCF77DecoderState::PhaseFound
                 } else {
+                    if self.datapos >= 60 {
+                        // Even a minute with a leap second has no more than 60 bits, so the minute
+                        // marker was missed and the bits can't be placed anymore
+                        self.datapos = 0;
+                        self.confidencesum = 0;
+                        self.faultcount = 0;
+                        self.goodminutes = 0;
+                    }
+
                     let datapos = self.datapos;
                     self.datapos += 1;
-                    if self.highcount > 3 {
+                    self.framecomplete = false;
+
+                    // Clear the bits not received yet so a partial frame never contains stale data
+                    // from a previous cycle
+                    self.data &= !(u64::MAX << datapos);
+
+                    let state = if self.highcount >= self.thresholds.one_min
+                        && datapos == 0
+                        && self.markerseen
+                    {
+                        // The start bit is always 0, so the minute marker was misdetected and the
+                        // next bit is taken as start bit instead
+                        self.datapos = 0;
+                        self.fault(FaultKind::StartBitSet)
+                    } else if self.highcount >= self.thresholds.one_min {
                         self.data |= 1 << datapos;
+                        self.confidence =
+                            ((self.lowcount + self.highcount) * 100 / self.bitsamples) as u8;
                         SimpleDCF77DecoderState::BitReceived
-                    } else if self.lowcount > 3 {
+                    } else if self.lowcount >= self.thresholds.zero_min {
                         self.data &= !(1 << datapos);
+                        let lowsamples = self.bitsamples - self.splitsamples;
+                        self.confidence = ((self.lowcount + lowsamples - self.highcount) * 100
+                            / self.bitsamples) as u8;
                         SimpleDCF77DecoderState::BitReceived
                     } else {
                         // Bad signal, let's continue with the next bit
-                        SimpleDCF77DecoderState::FaultyBit
-                    }
+                        self.fault(FaultKind::AmbiguousBit)
+                    };
+                    self.confidencesum = self.confidencesum.saturating_add(self.confidence as u16);
+                    state
                 }
             }
+            SimpleDCF77DecoderState::BitReceived | SimpleDCF77DecoderState::Idle
+                if self.pulselength > self.longsamples =>
+            {
+                // A pulse far longer than 200ms is a glitch rather than a 1 bit
+                self.data &= !(1 << self.datapos.saturating_sub(1));
+                self.confidencesum = self.confidencesum.saturating_sub(self.confidence as u16);
+                self.fault(FaultKind::NoiseAfterBit)
+            }
             SimpleDCF77DecoderState::BitReceived | SimpleDCF77DecoderState::Idle => {
                 if bit {
                     self.idlecount += 1;
                 }
 
-                if self.scancount >= 90 {
-                    if self.idlecount > 10 {
+                if self.scancount >= self.idlesamples {
+                    if self.idlecount > self.noisesamples {
                         self.idlecount = 0;
                         self.scancount = 0;
+                        self.fault(FaultKind::NoisySecond);
                     }
                     SimpleDCF77DecoderState::WaitingForPhase
                 } else {
@@ -564,6 +1997,1232 @@ impl SimpleDCF77Decoder {
             }
         };
 
-        self.scancount += 1;
+        #[cfg(feature = "event-log")]
+        self.log_event(previous);
+
+        let samplespersecond = 1000 / self.periodms;
+        if self.dutysamples < samplespersecond {
+            self.dutysamples += 1;
+            if bit {
+                self.dutyhigh += 1;
+            }
+            if self.dutysamples == samplespersecond {
+                self.dutycycle = (self.dutyhigh as u32 * 100 / samplespersecond as u32) as u8;
+            }
+        }
+
+        self.scancount = self.scancount.saturating_add(1);
+        self.samplecount = self.samplecount.wrapping_add(1);
+    }
+
+    /// Read the latest sample from the input pin the receiver is connected to and ingest it like
+    /// `read_bit`, a high level being passed as `true`
+    #[cfg(feature = "embedded-hal")]
+    pub fn poll<P: InputPin>(&mut self, pin: &mut P) -> Result<(), P::Error> {
+        let bit = pin.is_high()?;
+        self.read_bit(bit);
+
+        Ok(())
+    }
+
+    /// Ingest a batch of consecutive samples like calling `read_bit` for each of them, e.g. to
+    /// replay a recorded signal trace. Returns the number of bits recognized during the batch
+    pub fn feed_samples(&mut self, samples: &[bool]) -> usize {
+        let mut bits = 0;
+
+        for &sample in samples {
+            self.read_bit(sample);
+            if self.bit_complete() {
+                bits += 1;
+            }
+        }
+
+        bits
+    }
+
+    /// Measure the length of a second as the number of samples since the previous pulse and adjust
+    /// the idle and minute marker windows accordingly, so they stay in place even if the sample
+    /// clock runs too fast or too slow. The bit window keeps its nominal length: the high samples
+    /// of a pulse stretched by a fast clock spill into the idle window, where they only count
+    /// towards the noise of the current second. Intervals deviating more than 20% from the nominal
+    /// second, e.g. the minute marker or a glitch, are ignored
+    fn track_second_length(&mut self) {
+        let nominal = (1000 / self.periodms) as u32;
+        let interval = self.scancount as u32;
+
+        if interval * 5 < nominal * 4 || interval * 5 > nominal * 6 {
+            return;
+        }
+
+        // Smooth the measurement in fixed point with 4 fractional bits
+        self.secondlength = (self.secondlength * 3 + interval * 16) / 4;
+        let second = self.secondlength / 16;
+        self.idlesamples = (second * 9 / 10) as u16;
+        self.markersamples = (second * 18 / 10) as u16;
+    }
+
+    /// Record a faulty bit of the given kind and return the `FaultyBit` state
+    fn fault(&mut self, kind: FaultKind) -> SimpleDCF77DecoderState {
+        self.framecomplete = false;
+        self.confidence = 0;
+        self.faultcount = self.faultcount.saturating_add(1);
+        self.goodminutes = 0;
+        self.lastfault = Some(kind);
+
+        SimpleDCF77DecoderState::FaultyBit
+    }
+
+    /// Return the debounced signal level after ingesting the latest sample
+    fn filter(&mut self, bit: bool) -> bool {
+        if bit == self.filteredlevel {
+            self.debouncecount = 0;
+        } else {
+            self.debouncecount += 1;
+            if self.debouncecount >= self.debounce {
+                self.filteredlevel = bit;
+                self.debouncecount = 0;
+            }
+        }
+
+        self.filteredlevel
+    }
+
+    /// Finish the current cycle after its minute marker was detected
+    fn complete_cycle(&mut self) {
+        self.frameconfidence = if self.datapos > 0 {
+            (self.confidencesum / self.datapos as u16) as u8
+        } else {
+            0
+        };
+        self.confidencesum = 0;
+        self.cycleerrors = self.faultcount;
+        self.faultcount = 0;
+        self.cyclelength = self.datapos;
+        if self.markerseen && !self.complete_minute() {
+            self.lastfault = Some(FaultKind::PhaseTimeout);
+        }
+        let decoded = DCF77Time::with_received_bits(self.raw_frame(), self.datapos)
+            .decode()
+            .is_ok();
+        self.goodminutes = if self.cycleerrors == 0 && decoded && self.complete_minute() {
+            self.goodminutes.saturating_add(1)
+        } else {
+            0
+        };
+        self.framecomplete = self.complete_minute();
+        self.lastbituncertain = self.datapos == 58 || self.confidence < 50;
+        self.datapos = 0;
+        self.markerseen = true;
+    }
+
+    /// Drop the current cycle after a gap which doesn't match the configured marker bounds
+    fn discard_cycle(&mut self) {
+        self.framecomplete = false;
+        self.markeranomaly = true;
+        self.lastfault = Some(FaultKind::PhaseTimeout);
+        self.confidencesum = 0;
+        self.faultcount = 0;
+        self.goodminutes = 0;
+        self.datapos = 0;
+    }
+
+    /// Record the event corresponding to a change from the previous into the current state
+    #[cfg(feature = "event-log")]
+    fn log_event(&mut self, previous: SimpleDCF77DecoderState) {
+        if self.markerclosed {
+            self.events.push(self.samplecount, DecoderEvent::EndOfCycle);
+        }
+
+        if previous == self.state {
+            return;
+        }
+
+        let event = match self.state {
+            SimpleDCF77DecoderState::PhaseFound => DecoderEvent::PhaseFound,
+            SimpleDCF77DecoderState::BitReceived => DecoderEvent::BitReceived {
+                value: (self.data >> (self.datapos - 1)) & 1 != 0,
+            },
+            SimpleDCF77DecoderState::FaultyBit => DecoderEvent::FaultyBit,
+            SimpleDCF77DecoderState::EndOfCycle => DecoderEvent::EndOfCycle,
+            _ => return,
+        };
+        self.events.push(self.samplecount, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use testutil::*;
+    use *;
+
+    #[test]
+    fn rewound_bit_is_received_again() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, sent, 0, 21, 10);
+        // The 1 of the minute units in second 21 arrives as a 0
+        second(&mut samples, 100, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        decoder.feed_samples(&samples);
+        assert_eq!(decoder.seconds(), 22);
+        assert_eq!(decoder.latest_bit(), Some(false));
+
+        decoder.rewind_one_bit();
+        assert_eq!(decoder.seconds(), 21);
+        assert_eq!(decoder.latest_bit(), Some(true));
+
+        samples.clear();
+        bits(&mut samples, sent, 21, 59, 10);
+        second(&mut samples, 0, 10);
+        assert_eq!(
+            decode_all(&mut decoder, &samples),
+            [Ok(time(2024, 3, 15, 13, 37, false))]
+        );
+    }
+
+    #[test]
+    fn validity_mask_flags_failing_checks() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mask = |bits| DCF77Time::new(bits).validity_mask();
+
+        assert_eq!(mask(sent), DCF77Time::VALID_ALL);
+        assert_eq!(mask(sent | 1), DCF77Time::VALID_ALL & !DCF77Time::VALID_START);
+        assert_eq!(mask(sent & !(1 << 20)), DCF77Time::VALID_ALL & !DCF77Time::VALID_FRAMING);
+        assert_eq!(
+            mask(sent ^ (1 << 28)),
+            DCF77Time::VALID_ALL & !DCF77Time::VALID_MINUTE_PARITY
+        );
+        assert_eq!(
+            mask(sent ^ (1 << 30) ^ (1 << 58)),
+            DCF77Time::VALID_ALL & !DCF77Time::VALID_HOUR_PARITY & !DCF77Time::VALID_DATE_PARITY
+        );
+
+        let mut monday = time(2024, 3, 15, 13, 37, false);
+        monday.weekday = 1;
+        assert_eq!(
+            mask(DCF77Time::encode(&monday).bits()),
+            DCF77Time::VALID_ALL & !DCF77Time::VALID_WEEKDAY
+        );
+
+        // Minute units of 10, which keeps the parity and the minute value below 60
+        let units = frame(&time(2024, 3, 15, 13, 30, false)) | (1 << 22) | (1 << 24);
+        assert_eq!(mask(units), DCF77Time::VALID_ALL & !DCF77Time::VALID_BCD);
+    }
+
+    #[test]
+    fn second_alignment_error_follows_a_known_offset() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        for &(delay, expected) in &[(0, 0), (13, 130), (50, 500), (70, -300), (99, -10)] {
+            let mut samples = Vec::new();
+            samples.resize(delay, false);
+            samples.extend(signal(&[sent]));
+
+            let mut decoder = SimpleDCF77Decoder::new();
+            for sample in samples {
+                decoder.read_bit(sample);
+            }
+            assert_eq!(decoder.second_alignment_error_ms(), expected, "delay {}", delay);
+        }
+    }
+
+    #[test]
+    fn decode_partial_keeps_the_time_of_a_broken_date() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, true));
+        for &(broken, error) in &[
+            (sent ^ (1 << 58), DCF77Error::ParityMismatch),
+            (sent ^ (1 << 49) ^ (1 << 58), DCF77Error::ValueOutOfRange(FieldKind::Month)),
+        ] {
+            let partial = DCF77Time::new(broken).decode_partial();
+            assert_eq!(partial.start, Ok(()));
+            assert_eq!(partial.cest, Ok(true));
+            assert_eq!(partial.minute, Ok(37));
+            assert_eq!(partial.hour, Ok(13));
+            assert_eq!(partial.date, Err(error));
+        }
+
+        let partial = DCF77Time::new(sent).decode_partial();
+        assert_eq!(partial.date, Ok((2024, 3, 15, 5)));
+    }
+
+    #[test]
+    fn aux_bits_returns_the_requested_range() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, true));
+        let received = DCF77Time::new(sent | 0b01_0100_1101_0100 << 1);
+
+        assert_eq!(received.aux_bits(1, 14), Some(0b01_0100_1101_0100));
+        assert_eq!(received.aux_bits(3, 4), Some(0b0101));
+        assert_eq!(received.aux_bits(17, 2), Some(0b01));
+        assert_eq!(received.aux_bits(0, 59), Some(received.bits()));
+        assert_eq!(received.aux_bits(58, 1), Some(received.bits() >> 58));
+
+        assert_eq!(received.aux_bits(5, 0), None);
+        assert_eq!(received.aux_bits(0, 60), None);
+        assert_eq!(received.aux_bits(58, 2), None);
+        assert_eq!(received.aux_bits(59, 1), None);
+        assert_eq!(received.aux_bits(usize::MAX, 1), None);
+    }
+
+    #[test]
+    fn inverted_capture_decodes_like_the_frame() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        // Capture with inverted bit values and garbage above the 59 bits of the frame
+        let capture = !sent;
+
+        let received = DCF77Time::from_inverted(capture);
+        assert_eq!(received.bits(), sent);
+        assert_eq!(received.decode(), Ok(time(2024, 3, 15, 13, 37, false)));
+        assert_eq!(DCF77Time::new(capture).validate_start(), Err(DCF77Error::StartBitSet));
+    }
+
+    #[test]
+    fn raw_frame_masks_a_stale_leap_second_bit() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        // A noisy 60th bit, which should be 0 in a leap second
+        bits(&mut samples, sent | (1 << 59), 0, 60, 10);
+        second(&mut samples, 0, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        for sample in samples {
+            decoder.read_bit(sample);
+        }
+
+        assert_eq!(decoder.cycle_length(), 60);
+        assert_eq!(decoder.raw_data(), sent | (1 << 59));
+        assert_eq!(decoder.raw_frame(), sent);
+        let decoded = DCF77Time::new(decoder.raw_frame()).decode();
+        assert_eq!(decoded, Ok(time(2024, 3, 15, 13, 37, false)));
+    }
+
+    #[test]
+    fn stale_bits_do_not_leak_into_a_new_partial_frame() {
+        let previous = frame(&time(2024, 12, 31, 23, 59, false));
+        let current = frame(&time(2025, 1, 1, 0, 0, false));
+        let mut samples = signal(&[previous]);
+        bits(&mut samples, current, 0, 30, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        for sample in samples {
+            decoder.read_bit(sample);
+        }
+
+        assert_eq!(decoder.seconds(), 30);
+        // The previous frame has bits set beyond the 30 bits received so far
+        assert_ne!(previous >> 30, 0);
+        assert_eq!(decoder.raw_data(), current & ((1 << 30) - 1));
+        assert_eq!(decoder.received_frame().minutes(), Ok(0));
+        assert_eq!(decoder.received_frame().hours(), Err(DCF77Error::FrameIncomplete));
+    }
+
+    #[test]
+    fn decode_fast_matches_decode() {
+        let sent = frame(&time(2024, 2, 29, 23, 59, true));
+        let mut frames = Vec::new();
+        frames.push(DCF77Time::new(sent));
+        frames.push(DCF77Time::with_received_bits(sent, 58));
+        for bit in 0..59 {
+            frames.push(DCF77Time::new(sent ^ (1 << bit)));
+            // Keep the date parity intact to reach the range checks
+            frames.push(DCF77Time::new(sent ^ (1 << bit) ^ (1 << 58)));
+        }
+
+        for received in frames {
+            assert_eq!(received.decode_fast(), received.decode(), "{:#x}", received.bits());
+        }
+    }
+
+    #[test]
+    fn checked_pairs_of_valid_and_parity_broken_frames() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let valid = DCF77Time::new(sent);
+        assert_eq!(valid.minute_checked_pair(), (37, true));
+        assert_eq!(valid.hour_checked_pair(), (13, true));
+        assert_eq!(valid.date_checked_pair(), ((2024, 3, 15, 5), true));
+
+        let broken = DCF77Time::new(sent ^ (1 << 28) ^ (1 << 29) ^ (1 << 58));
+        assert_eq!(broken.minute_checked_pair(), (37, false));
+        assert_eq!(broken.hour_checked_pair(), (12, false));
+        assert_eq!(broken.date_checked_pair(), ((2024, 3, 15, 5), false));
+
+        // The pairs of the other fields aren't affected by a broken parity
+        let minute = DCF77Time::new(sent ^ (1 << 21));
+        assert_eq!(minute.minute_checked_pair(), (36, false));
+        assert_eq!(minute.hour_checked_pair(), (13, true));
+        assert_eq!(minute.date_checked_pair(), ((2024, 3, 15, 5), true));
+    }
+
+    #[test]
+    fn read_bit_ts_stores_the_tick_of_the_completing_sample() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut completed = Vec::new();
+
+        for (index, sample) in signal(&[sent]).into_iter().enumerate() {
+            let tick = 5000 + index as u32 * 10;
+            decoder.read_bit_ts(sample, tick);
+            if decoder.bit_complete() {
+                completed.push(tick);
+                assert_eq!(decoder.last_bit_tick(), Some(tick));
+            } else if index < 100 {
+                assert_eq!(decoder.last_bit_tick(), None);
+            } else {
+                assert_eq!(decoder.last_bit_tick(), completed.last().cloned());
+            }
+        }
+
+        // The first bit completes 200ms after the rising edge one second into the signal
+        assert_eq!(completed.len(), 59);
+        assert_eq!(completed[0], 5000 + 1200);
+        assert_eq!(completed[1], 5000 + 2200);
+    }
+
+    #[test]
+    fn marginal_last_bit_is_uncertain() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        assert_eq!(sent >> 58, 1);
+
+        // The high samples of bit 58: a regular 200ms pulse, a barely recognized 1 bit whose
+        // pulse drops out after 20ms and only comes back after 100ms, and no pulse at all
+        let regular: &[(usize, usize)] = &[(0, 20)];
+        let marginal: &[(usize, usize)] = &[(0, 2), (10, 15)];
+        let missing: &[(usize, usize)] = &[];
+        for &(pulses, length, uncertain) in
+            &[(regular, 59, false), (marginal, 59, true), (missing, 58, true)]
+        {
+            let mut samples = Vec::new();
+            second(&mut samples, 0, 10);
+            bits(&mut samples, sent, 0, 58, 10);
+            for sample in 0..100 {
+                samples.push(pulses.iter().any(|&(from, to)| (from..to).contains(&sample)));
+            }
+            second(&mut samples, 0, 10);
+
+            let mut decoder = SimpleDCF77Decoder::new();
+            let decoded = decode_all(&mut decoder, &samples);
+
+            assert_eq!(decoder.cycle_length(), length);
+            assert_eq!(decoder.last_bit_uncertain(), uncertain, "{:?}", pulses);
+            if length == 59 {
+                assert_eq!(decoded, [Ok(time(2024, 3, 15, 13, 37, false))]);
+            }
+        }
+    }
+
+    #[test]
+    fn encoded_frames_have_the_expected_fixed_bits() {
+        for sent in &[
+            time(2024, 3, 15, 13, 37, false),
+            time(2024, 7, 1, 0, 0, true),
+            time(2099, 12, 31, 23, 59, false),
+        ] {
+            let encoded = DCF77Time::encode(sent);
+            for &(position, value) in DCF77Time::expected_fixed_bits() {
+                assert_eq!(encoded.bit(position), Some(value), "bit {} of {:?}", position, sent);
+                let flipped = DCF77Time::new(encoded.bits() ^ (1 << position));
+                assert_ne!(flipped.validity_mask(), DCF77Time::VALID_ALL);
+            }
+        }
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn event_log_records_state_changes() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, sent | 0b10, 0, 2, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        for &sample in &samples {
+            decoder.read_bit(sample);
+        }
+
+        let logged = |sample, event| LoggedEvent { sample, event };
+        assert_eq!(
+            decoder.events().collect::<Vec<_>>(),
+            [
+                logged(100, DecoderEvent::PhaseFound),
+                logged(120, DecoderEvent::BitReceived { value: false }),
+                logged(200, DecoderEvent::PhaseFound),
+                logged(220, DecoderEvent::BitReceived { value: true }),
+            ]
+        );
+
+        // Once full after completing the minute, the oldest events are overwritten
+        let mut samples = Vec::new();
+        bits(&mut samples, sent, 2, 59, 10);
+        second(&mut samples, 0, 10);
+        for sample in samples {
+            decoder.read_bit(sample);
+        }
+        let events = decoder.events().collect::<Vec<_>>();
+        assert_eq!(events.len(), EVENT_LOG_CAPACITY);
+        assert_eq!(events[EVENT_LOG_CAPACITY - 1].event, DecoderEvent::EndOfCycle);
+        assert!(events.windows(2).all(|pair| pair[0].sample <= pair[1].sample));
+
+        decoder.clear_events();
+        assert_eq!(decoder.events().count(), 0);
+    }
+
+    #[test]
+    fn decode_options_combined() {
+        let checked = |frame: &DateTime, century, strict, check_weekday| {
+            DCF77Time::encode(frame).decode_with_options(DecodeOptions {
+                century,
+                strict,
+                check_weekday,
+            })
+        };
+
+        let friday = time(2024, 3, 15, 13, 37, false);
+        assert_eq!(checked(&friday, 2000, true, true), Ok(friday));
+        // 1924-03-15 was a Saturday
+        assert_eq!(checked(&friday, 1900, true, false).map(|t| t.year), Ok(1924));
+        assert_eq!(checked(&friday, 1900, true, true), Err(DCF77Error::WeekdayInconsistent));
+        let mut saturday = friday;
+        saturday.weekday = 6;
+        let archived = checked(&saturday, 1900, true, true).unwrap();
+        assert_eq!((archived.year, archived.day, archived.weekday), (1924, 15, 6));
+
+        // February 29th exists in 2000 but not in 1900
+        let leap = time(2000, 2, 29, 12, 0, false);
+        assert!(checked(&leap, 1900, false, false).is_ok());
+        assert_eq!(
+            checked(&leap, 1900, true, false),
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Day))
+        );
+
+        let mut day_zero = friday;
+        day_zero.day = 0;
+        assert!(checked(&day_zero, 2000, false, false).is_ok());
+        assert_eq!(
+            checked(&day_zero, 2000, true, true),
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Day))
+        );
+
+        assert_eq!(
+            checked(&friday, u16::MAX, false, false),
+            Err(DCF77Error::ValueOutOfRange(FieldKind::Year))
+        );
+    }
+
+    #[test]
+    fn lenient_minutes_and_hours_only_check_the_parity() {
+        let sent = frame(&time(2024, 3, 15, 13, 30, false));
+        let valid = DCF77Time::new(sent);
+        assert_eq!(valid.minutes_lenient(), (30, true));
+        assert_eq!(valid.hours_lenient(), (13, true));
+
+        let broken = DCF77Time::new(sent ^ (1 << 21) ^ (1 << 35));
+        assert_eq!(broken.minutes_lenient(), (31, false));
+        assert_eq!(broken.hours_lenient(), (13, false));
+        assert!(broken.minutes().is_err() && broken.hours().is_err());
+
+        // Minute 70 with a matching parity is out of range but passes the lenient check
+        let range = DCF77Time::new(sent ^ (1 << 27) ^ (1 << 28));
+        assert_eq!(range.minutes_lenient(), (70, true));
+        assert_eq!(range.minutes(), Err(DCF77Error::ValueOutOfRange(FieldKind::Minute)));
+    }
+
+    #[test]
+    fn long_pulse_is_noise_rather_than_a_1_bit() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        for &position in &[0, 5] {
+            let mut samples = Vec::new();
+            second(&mut samples, 0, 10);
+            bits(&mut samples, sent, 0, position, 10);
+            second(&mut samples, 500, 10);
+
+            let mut decoder = SimpleDCF77Decoder::new();
+            for sample in samples {
+                decoder.read_bit(sample);
+            }
+
+            assert_eq!(decoder.last_fault(), Some(FaultKind::NoiseAfterBit));
+            assert_eq!(decoder.raw_data() >> position, 0, "bit {}", position);
+            assert_eq!(decoder.raw_data(), sent & ((1 << position) - 1));
+        }
+    }
+
+    #[test]
+    fn scattered_noise_between_pulses_is_a_noisy_second() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, sent, 0, 5, 10);
+        // Short spikes every other sample after the pulse of the last bit
+        for sample in samples.len() - 70..samples.len() - 30 {
+            samples[sample] = sample % 2 == 0;
+        }
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        for sample in samples {
+            decoder.read_bit(sample);
+        }
+
+        assert_eq!(decoder.last_fault(), Some(FaultKind::NoisySecond));
+        assert_eq!(decoder.raw_data(), sent & 0x1f);
+    }
+
+    #[test]
+    fn millis_into_minute_advance_and_reset_at_the_marker() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = signal(&[sent]);
+        bits(&mut samples, sent, 0, 2, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut millis = Vec::new();
+        for sample in samples {
+            decoder.read_bit(sample);
+            millis.push(decoder.now_millis_into_minute());
+        }
+
+        // Nothing is known before the first minute marker
+        assert!(millis[..6100].iter().all(|&ms| ms == 0));
+        // The minute starts with the rising edge of the start bit
+        assert_eq!(&millis[6100..6103], [0, 10, 20]);
+        assert_eq!(millis.last(), Some(&1990));
+
+        // A long running decoder wraps instead of overflowing
+        decoder.minutestart = Some(0);
+        decoder.samplecount = u32::MAX;
+        assert_eq!(decoder.now_millis_into_minute(), (u32::MAX - 1).wrapping_mul(10));
+    }
+
+    #[test]
+    fn ordinal_date_at_year_boundaries() {
+        let ordinal = |year, month, day| {
+            DCF77Time::new(frame(&time(year, month, day, 12, 0, false))).ordinal_date()
+        };
+
+        assert_eq!(ordinal(2024, 1, 1), Ok((2024, 1)));
+        assert_eq!(ordinal(2024, 2, 29), Ok((2024, 60)));
+        assert_eq!(ordinal(2024, 3, 1), Ok((2024, 61)));
+        assert_eq!(ordinal(2024, 12, 31), Ok((2024, 366)));
+        assert_eq!(ordinal(2025, 1, 1), Ok((2025, 1)));
+        assert_eq!(ordinal(2025, 3, 1), Ok((2025, 60)));
+        assert_eq!(ordinal(2025, 12, 31), Ok((2025, 365)));
+        assert_eq!(ordinal(2000, 12, 31), Ok((2000, 366)));
+    }
+
+    #[test]
+    fn duty_cycle_of_synthetic_pulses() {
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        for &sample in &samples {
+            decoder.read_bit(sample);
+        }
+        assert_eq!(decoder.last_bit_duty_cycle(), 0);
+
+        for &pulse_ms in &[100, 200, 150, 90, 250] {
+            let mut samples = Vec::new();
+            second(&mut samples, pulse_ms, 10);
+            for &sample in &samples {
+                decoder.read_bit(sample);
+            }
+            assert_eq!(decoder.last_bit_duty_cycle(), (pulse_ms / 10) as u8);
+        }
+    }
+
+    #[test]
+    fn marker_gap_bounds_just_inside_and_outside() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        for &(gap_ms, within) in &[(1890, false), (1900, true), (2100, true), (2110, false)] {
+            let mut samples = Vec::new();
+            second(&mut samples, 0, 10);
+            bits(&mut samples, sent, 0, 59, 10);
+            samples.resize(samples.len() + (gap_ms - 1000) / 10, false);
+            let edge = samples.len();
+            second(&mut samples, 100, 10);
+
+            let mut decoder = SimpleDCF77Decoder::new();
+            decoder.set_marker_gap_bounds_ms(Some((1900, 2100)));
+            let mut ended = Vec::new();
+            for (index, &sample) in samples.iter().enumerate() {
+                decoder.read_bit(sample);
+                if decoder.cycle_ended() {
+                    ended.push(index);
+                    assert_eq!(decoder.received_frame().bits(), sent);
+                }
+            }
+
+            if within {
+                // The end of the cycle is only known with the pulse starting the next minute
+                assert_eq!(ended, [edge], "{}ms", gap_ms);
+                assert!(!decoder.marker_anomaly());
+            } else {
+                assert_eq!(ended, [], "{}ms", gap_ms);
+                assert!(decoder.marker_anomaly());
+            }
+        }
+    }
+
+    #[test]
+    fn field_bit_count_of_the_minute() {
+        let count = |minute| {
+            DCF77Time::new(frame(&time(2024, 3, 15, 13, minute, false)))
+                .field_bit_count(FieldKind::Minute)
+        };
+
+        assert_eq!(count(0), 0);
+        assert_eq!(count(37), 5);
+        assert_eq!(count(59), 4);
+
+        // The parity bit isn't counted, the other bits of the frame neither
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let received = DCF77Time::new(sent ^ (1 << 28) ^ (1 << 20) ^ (1 << 29));
+        assert_eq!(received.field_bit_count(FieldKind::Minute), 5);
+        assert_eq!(DCF77Time::new(sent ^ (1 << 21)).field_bit_count(FieldKind::Minute), 4);
+    }
+
+    #[test]
+    fn best_frame_corrects_a_phase_lock_one_second_off() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut spurious = Vec::new();
+        second(&mut spurious, 0, 10);
+        second(&mut spurious, 100, 10);
+        minute(&mut spurious, sent, 10);
+        let mut missed = Vec::new();
+        second(&mut missed, 0, 10);
+        second(&mut missed, 0, 10);
+        bits(&mut missed, sent, 1, 59, 10);
+        second(&mut missed, 0, 10);
+
+        for &(ref samples, length, alignment) in
+            &[(spurious, 60, Alignment::DroppedFirst), (missed, 58, Alignment::InsertedStart)]
+        {
+            let mut decoder = SimpleDCF77Decoder::new();
+            let mut clock = DCF77Clock::new();
+            let mut best = None;
+            for &sample in samples {
+                decoder.read_bit(sample);
+                clock.read_bit(sample);
+                // The silence in front of the missed start bit already ends an empty cycle
+                if decoder.cycle_ended() && decoder.cycle_length() > 0 {
+                    best = decoder.best_frame();
+                    assert_eq!(decoder.cycle_length(), length);
+                    assert!(decoder.received_frame().decode().is_err());
+                }
+            }
+
+            assert_eq!(best, Some((DCF77Time::new(sent), alignment)));
+            assert_eq!(clock.alignment(), alignment);
+            assert!(clock.phase_corrected());
+            assert_eq!(clock.time(), Some(time(2024, 3, 15, 13, 37, false)));
+        }
+    }
+
+    #[test]
+    fn best_frame_does_not_retry_a_well_framed_invalid_frame() {
+        // Shifted down by one bit, the odd minute moves into bit 20 and the start bit stays 0
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut best = None;
+        for sample in signal(&[sent >> 1]) {
+            decoder.read_bit(sample);
+            if decoder.cycle_ended() {
+                best = Some(decoder.best_frame());
+            }
+        }
+
+        assert_eq!(decoder.cycle_length(), 59);
+        assert!(DCF77Time::new(sent >> 1).decode().is_err());
+        assert!(DCF77Time::new((sent >> 1) << 1).decode().is_ok());
+        assert_eq!(best, Some(None));
+    }
+
+    #[test]
+    fn interpret_valid_and_invalid_frames() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let valid = DCF77Time::new(sent).interpret();
+        assert_eq!(valid.time, time(2024, 3, 15, 13, 37, false));
+        assert_eq!(valid.validity, DCF77Time::VALID_ALL);
+        assert!(valid.is_trustworthy);
+        assert_eq!(valid.fields.minute, Ok(37));
+
+        // The best-effort time is still shown for a broken hour parity
+        let invalid = DCF77Time::new(sent ^ (1 << 35)).interpret();
+        assert_eq!(invalid.time, time(2024, 3, 15, 13, 37, false));
+        assert_eq!(invalid.validity, DCF77Time::VALID_ALL & !DCF77Time::VALID_HOUR_PARITY);
+        assert!(!invalid.is_trustworthy);
+        assert_eq!(invalid.fields.minute, Ok(37));
+        assert_eq!(invalid.fields.hour, Err(DCF77Error::ParityMismatch));
+
+        let truncated = DCF77Time::with_received_bits(sent, 40).interpret();
+        assert!(!truncated.is_trustworthy);
+        assert_eq!(truncated.fields.hour, Ok(13));
+        assert_eq!(truncated.fields.date, Err(DCF77Error::FrameIncomplete));
+    }
+
+    #[test]
+    fn dropout_ending_a_cycle_is_a_phase_timeout() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut decoder = SimpleDCF77Decoder::new();
+
+        // Joining in the middle of a minute isn't a fault
+        let mut samples = Vec::new();
+        bits(&mut samples, sent, 30, 59, 10);
+        second(&mut samples, 0, 10);
+        minute(&mut samples, sent, 10);
+        decode_all(&mut decoder, &samples);
+        assert_eq!(decoder.last_fault(), None);
+
+        let mut samples = Vec::new();
+        bits(&mut samples, sent, 0, 30, 10);
+        second(&mut samples, 0, 10);
+        second(&mut samples, 0, 10);
+        decode_all(&mut decoder, &samples);
+        assert_eq!(decoder.cycle_length(), 30);
+        assert_eq!(decoder.last_fault(), Some(FaultKind::PhaseTimeout));
+        assert!(!decoder.bit_faulty());
+
+        decoder.clear_faults();
+        assert_eq!(decoder.last_fault(), None);
+    }
+
+    #[test]
+    fn gap_outside_the_marker_bounds_is_a_phase_timeout() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = signal(&[sent]);
+        bits(&mut samples, sent, 0, 10, 10);
+        // A gap of 1.6s between two pulses, too short for a minute marker
+        samples.resize(samples.len() + 60, false);
+        second(&mut samples, 100, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        decoder.set_marker_gap_bounds_ms(Some((1900, 2100)));
+        decode_all(&mut decoder, &samples);
+        assert!(decoder.marker_anomaly());
+        assert_eq!(decoder.last_fault(), Some(FaultKind::PhaseTimeout));
+    }
+
+    #[test]
+    fn weekday_consistency_of_the_date() {
+        let friday = time(2024, 3, 15, 13, 37, false);
+        assert_eq!(DCF77Time::encode(&friday).weekday_consistent(), Ok(true));
+
+        let mut sunday = friday;
+        sunday.weekday = 7;
+        let received = DCF77Time::encode(&sunday);
+        assert_eq!(received.weekday_consistent(), Ok(false));
+        assert_eq!(received.validate_weekday_consistency(), Err(DCF77Error::WeekdayInconsistent));
+
+        let broken = DCF77Time::new(frame(&friday) ^ (1 << 58));
+        assert_eq!(broken.weekday_consistent(), Err(DCF77Error::ParityMismatch));
+    }
+
+    #[test]
+    fn date_checks_the_length_of_the_month() {
+        let date = |year, month, day| {
+            let mut sent = time(year, month, 1, 12, 0, false);
+            sent.day = day;
+            DCF77Time::encode(&sent).date().map(|(year, month, day, _)| (year, month, day))
+        };
+
+        assert_eq!(date(2000, 2, 29), Ok((2000, 2, 29)));
+        assert_eq!(date(2024, 2, 29), Ok((2024, 2, 29)));
+        assert_eq!(date(2023, 2, 28), Ok((2023, 2, 28)));
+        assert_eq!(date(2023, 2, 29), Err(DCF77Error::ValueOutOfRange(FieldKind::Day)));
+        assert_eq!(date(2024, 4, 30), Ok((2024, 4, 30)));
+        assert_eq!(date(2024, 4, 31), Err(DCF77Error::ValueOutOfRange(FieldKind::Day)));
+        assert_eq!(date(2024, 5, 31), Ok((2024, 5, 31)));
+    }
+
+    #[test]
+    fn unix_timestamp_of_known_dates() {
+        let winter = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        assert_eq!(winter.unix_timestamp(), Ok(1_710_506_220));
+
+        // 02:30 occurs twice when summer time ends, first as CEST and then again as CET
+        let first = DCF77Time::new(frame(&time(2024, 10, 27, 2, 30, true)));
+        let repeated = DCF77Time::new(frame(&time(2024, 10, 27, 2, 30, false)));
+        assert_eq!(first.unix_timestamp(), Ok(1_729_989_000));
+        assert_eq!(repeated.unix_timestamp(), Ok(1_729_992_600));
+
+        let corrupt = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)) ^ (1 << 21));
+        assert_eq!(corrupt.unix_timestamp(), Err(DCF77Error::ParityMismatch));
+    }
+
+    #[test]
+    fn leap_minute_with_an_inserted_second_decodes() {
+        let leap = frame(&time(2017, 1, 1, 1, 0, false)) | (1 << 19);
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, leap, 0, 59, 10);
+        // The inserted leap second is sent as 0 bit before the minute marker
+        second(&mut samples, 100, 10);
+        second(&mut samples, 0, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        let decoded = decode_all(&mut decoder, &samples);
+
+        assert_eq!(decoded, [Ok(time(2017, 1, 1, 1, 0, false))]);
+        assert_eq!(decoder.cycle_length(), 60);
+        assert!(decoder.was_leap_minute());
+    }
+
+    #[test]
+    fn decode_with_correction_of_single_and_double_flips() {
+        let expected = time(2024, 7, 1, 12, 0, true);
+        let good = frame(&expected);
+
+        // A valid frame passes through unchanged
+        assert_eq!(DCF77Time::new(good).decode_with_correction(), Ok(expected));
+
+        // Of all weekday candidates only Monday matches the date
+        let weekday = DCF77Time::new(good ^ (1 << 42));
+        assert_eq!(weekday.decode(), Err(DCF77Error::ParityMismatch));
+        assert_eq!(weekday.decode_with_correction(), Ok(expected));
+
+        // Every flipped minute bit gives a valid minute, so the correction is ambiguous
+        let minute = DCF77Time::new(good ^ (1 << 22));
+        assert_eq!(minute.decode_with_correction(), Err(DCF77Error::ParityMismatch));
+
+        // With two failing parities the error of decode is returned
+        let double = DCF77Time::new(good ^ (1 << 22) ^ (1 << 42));
+        assert_eq!(double.decode_with_correction(), double.decode());
+        assert_eq!(double.decode_with_correction(), Err(DCF77Error::ParityMismatch));
+    }
+
+    #[test]
+    fn debounce_filters_single_sample_glitches() {
+        let expected = time(2024, 3, 15, 13, 37, false);
+        let good = frame(&expected);
+        let mut samples = signal(&[good, good]);
+        // A single high sample shortly before the end of every 0 bit
+        for minute in 0..2 {
+            for bit in (0..59).filter(|&bit| (good >> bit) & 1 == 0) {
+                samples[100 + minute * 6000 + bit * 100 + 95] = true;
+            }
+        }
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        let broken = Err(DCF77Error::ParityMismatch);
+        assert_eq!(decode_all(&mut decoder, &samples), [broken, broken]);
+
+        let mut decoder = SimpleDCF77Decoder::with_debounce(2);
+        assert_eq!(decode_all(&mut decoder, &samples), [Ok(expected), Ok(expected)]);
+    }
+
+    #[test]
+    fn completed_frame_is_dropped_with_a_set_start_bit() {
+        let expected = time(2024, 3, 15, 13, 37, false);
+        let samples = signal(&[frame(&expected)]);
+
+        let mut taken = SimpleDCF77Decoder::new();
+        taken.feed_samples(&samples);
+        assert_eq!(taken.take_completed_frame(), Some(frame(&expected)));
+        assert_eq!(taken.take_completed_frame(), None);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        decoder.feed_samples(&samples);
+        assert_eq!(DCF77Time::try_from(&decoder).and_then(|time| time.decode()), Ok(expected));
+
+        // The minute marker is followed by a 1 as start bit
+        let mut start = Vec::new();
+        second(&mut start, 200, 10);
+        decoder.feed_samples(&start);
+        assert_eq!(decoder.last_fault(), Some(FaultKind::StartBitSet));
+        assert_eq!(DCF77Time::try_from(&decoder), Err(DCF77Error::FrameIncomplete));
+        assert_eq!(decoder.take_completed_frame(), None);
+    }
+
+    #[test]
+    fn sample_clock_drifting_either_way() {
+        let frames: Vec<u64> = (0..4)
+            .map(|minute| frame(&time(2024, 3, 15, 13, 37 + minute, false)))
+            .collect();
+        let nominal = signal(&frames);
+
+        for &rate in &[90, 95, 105, 110] {
+            let mut decoder = SimpleDCF77Decoder::new();
+            let decoded = decode_all(&mut decoder, &resample(&nominal, rate));
+
+            assert_eq!(decoded.len(), 4, "{} samples/s", rate);
+            assert!(decoded.iter().all(|time| time.is_ok()), "{} samples/s", rate);
+            assert_eq!(decoder.last_fault(), None, "{} samples/s", rate);
+        }
+    }
+
+    #[test]
+    fn plausible_successors_across_rollovers() {
+        let at = |year, month, day, hour, minute, cest| {
+            DCF77Time::new(frame(&time(year, month, day, hour, minute, cest)))
+        };
+
+        let previous = at(2024, 3, 15, 13, 37, false);
+        assert!(at(2024, 3, 15, 13, 38, false).is_plausible_successor(&previous));
+        assert!(!at(2024, 3, 15, 13, 39, false).is_plausible_successor(&previous));
+        assert!(!previous.is_plausible_successor(&at(2024, 3, 15, 13, 38, false)));
+
+        // Summer time ends, 02:59 CEST is followed by 02:00 CET
+        let summer = at(2024, 10, 27, 2, 59, true);
+        assert!(at(2024, 10, 27, 2, 0, false).is_plausible_successor(&summer));
+        assert!(!at(2024, 10, 27, 3, 0, false).is_plausible_successor(&summer));
+
+        let new_year = at(2025, 1, 1, 0, 0, false);
+        assert!(new_year.is_plausible_successor(&at(2024, 12, 31, 23, 59, false)));
+
+        let corrupt = DCF77Time::new(new_year.bits() ^ (1 << 21));
+        assert!(!corrupt.is_plausible_successor(&at(2024, 12, 31, 23, 59, false)));
+    }
+
+    #[test]
+    fn decode_verbose_flags_the_failing_field() {
+        let sent = frame(&time(2024, 4, 30, 13, 37, true));
+        let valid = DCF77Time::new(sent).decode_verbose();
+        assert_eq!((valid.day, valid.month, valid.year), (Ok(30), Ok(4), Ok(2024)));
+        assert_eq!((valid.minute, valid.hour, valid.weekday), (Ok(37), Ok(13), Ok(2)));
+
+        // April 31st with the date parity adjusted, only the day is out of range
+        let day = DCF77Time::new(sent ^ (1 << 36) ^ (1 << 58)).decode_verbose();
+        assert_eq!(day.day, Err(DCF77Error::ValueOutOfRange(FieldKind::Day)));
+        assert_eq!((day.month, day.year, day.weekday), (Ok(4), Ok(2024), Ok(2)));
+
+        // Month 14 with the date parity adjusted
+        let month = DCF77Time::new(sent ^ (1 << 49) ^ (1 << 58)).decode_verbose();
+        assert_eq!(month.month, Err(DCF77Error::ValueOutOfRange(FieldKind::Month)));
+        assert_eq!((month.day, month.year), (Ok(30), Ok(2024)));
+
+        // A broken date parity marks all date fields but neither the minute nor the hour
+        let parity = DCF77Time::new(sent ^ (1 << 58)).decode_verbose();
+        assert_eq!(parity.day, Err(DCF77Error::ParityMismatch));
+        assert_eq!(parity.year, Err(DCF77Error::ParityMismatch));
+        assert_eq!((parity.minute, parity.hour), (Ok(37), Ok(13)));
+
+        let minute = DCF77Time::new(sent ^ (1 << 21)).decode_verbose();
+        assert_eq!(minute.minute, Err(DCF77Error::ParityMismatch));
+        assert_eq!((minute.hour, minute.day), (Ok(13), Ok(30)));
+    }
+
+    #[test]
+    fn sampling_at_50_hz_tells_bits_apart() {
+        let mut decoder = SimpleDCF77Decoder::with_sample_period_ms(20);
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 20);
+        second(&mut samples, 100, 20);
+        decoder.feed_samples(&samples);
+        assert_eq!(decoder.latest_bit(), Some(false));
+
+        samples.clear();
+        second(&mut samples, 200, 20);
+        decoder.feed_samples(&samples);
+        assert_eq!(decoder.latest_bit(), Some(true));
+
+        let sent = time(2024, 3, 15, 13, 37, false);
+        let mut decoder = SimpleDCF77Decoder::with_sample_period_ms(20);
+        samples.clear();
+        second(&mut samples, 0, 20);
+        minute(&mut samples, frame(&sent), 20);
+        assert_eq!(decode_all(&mut decoder, &samples), [Ok(sent)]);
+    }
+
+    #[test]
+    fn state_names_at_key_transitions() {
+        let names = |decoder: &mut SimpleDCF77Decoder, samples: &[bool]| {
+            let mut names = Vec::new();
+            for &sample in samples {
+                decoder.read_bit(sample);
+                if names.last() != Some(&decoder.state_name()) {
+                    names.push(decoder.state_name());
+                }
+            }
+            names
+        };
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut decoder = SimpleDCF77Decoder::new();
+        assert_eq!(decoder.state_name(), "WaitingForPhase");
+
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, sent, 0, 1, 10);
+        assert_eq!(
+            names(&mut decoder, &samples),
+            ["WaitingForPhase", "PhaseFound", "BitReceived", "Idle", "WaitingForPhase"]
+        );
+
+        samples.clear();
+        second(&mut samples, 20, 10);
+        assert_eq!(
+            names(&mut decoder, &samples),
+            ["PhaseFound", "FaultyBit", "WaitingForPhase"]
+        );
+
+        samples.clear();
+        bits(&mut samples, sent, 2, 59, 10);
+        second(&mut samples, 0, 10);
+        assert!(names(&mut decoder, &samples).ends_with(&["EndOfCycle", "WaitingForPhase"]));
+    }
+
+    #[test]
+    fn progress_follows_the_received_bits() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        decoder.feed_samples(&samples);
+        assert_eq!(decoder.progress_percent(), 0);
+
+        for &(from, to, percent) in &[(0, 15, 25), (15, 30, 50), (30, 58, 98), (58, 59, 100)] {
+            samples.clear();
+            bits(&mut samples, sent, from, to, 10);
+            decoder.feed_samples(&samples);
+            assert_eq!(decoder.seconds(), to);
+            assert_eq!(decoder.progress_percent(), percent);
+        }
+
+        samples.clear();
+        second(&mut samples, 0, 10);
+        decoder.feed_samples(&samples);
+        assert!(decoder.received_frame().decode().is_ok());
+        assert_eq!(decoder.progress_percent(), 0);
+    }
+
+    #[test]
+    fn clearing_faults_keeps_the_position() {
+        let sent = frame(&time(2024, 3, 15, 13, 37, false));
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        bits(&mut samples, sent, 0, 5, 10);
+        second(&mut samples, 20, 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        for &sample in &samples {
+            decoder.read_bit(sample);
+            if decoder.bit_faulty() {
+                break;
+            }
+        }
+        assert!(decoder.bit_faulty());
+        assert_eq!(decoder.seconds(), 6);
+
+        decoder.clear_faults();
+        assert!(!decoder.bit_faulty());
+        assert_eq!(decoder.last_fault(), None);
+        assert_eq!(decoder.seconds(), 6);
+        assert_eq!(decoder.raw_frame(), sent & 0x1f);
+    }
+
+    #[test]
+    fn julian_day_of_known_dates() {
+        let jdn = |year, month, day| {
+            DCF77Time::new(frame(&time(year, month, day, 12, 0, false))).julian_day()
+        };
+
+        // The J2000.0 epoch is at noon of JDN 2451545
+        assert_eq!(jdn(2000, 1, 1), Ok(2_451_545));
+        assert_eq!(jdn(2024, 3, 15), Ok(2_460_385));
+        assert_eq!(jdn(2024, 2, 29), Ok(2_460_370));
+        assert_eq!(
+            DCF77Time::new(frame(&time(2024, 3, 15, 12, 0, false)) ^ (1 << 40)).julian_day(),
+            Err(DCF77Error::ParityMismatch)
+        );
+    }
+
+    #[test]
+    fn diff_of_consecutive_minutes() {
+        let previous = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let current = DCF77Time::new(frame(&time(2024, 3, 15, 13, 38, false)));
+
+        // Minute 37 is sent as 0110111 and minute 38 as 0111000, both with parity bit 1
+        assert_eq!(current.diff(&previous), 0b1111 << 21);
+        assert_eq!(previous.diff(&current), current.diff(&previous));
+        assert_eq!(current.diff(&current), 0);
+    }
+
+    #[test]
+    fn alarm_matches_only_the_decoded_time() {
+        let received = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+
+        assert_eq!(received.matches_alarm(13 * 60 + 37), Ok(true));
+        assert_eq!(received.matches_alarm(13 * 60 + 38), Ok(false));
+        assert_eq!(received.matches_alarm(37), Ok(false));
+        assert_eq!(received.matches_alarm(24 * 60 + 37), Ok(false));
+
+        let corrupt = DCF77Time::new(received.bits() ^ (1 << 30));
+        assert_eq!(corrupt.matches_alarm(13 * 60 + 37), Err(DCF77Error::ParityMismatch));
+    }
+
+    #[test]
+    fn truncated_frame_refuses_missing_fields() {
+        let bits = frame(&time(2024, 3, 15, 13, 37, false));
+
+        let truncated = DCF77Time::with_received_bits(bits, 28);
+        assert_eq!(truncated.minutes(), Err(DCF77Error::FrameIncomplete));
+        assert_eq!(truncated.received_bits(), 28);
+        assert_eq!(truncated.bits(), bits);
+
+        let minute = DCF77Time::with_received_bits(bits, 29);
+        assert_eq!(minute.minutes(), Ok(37));
+        assert_eq!(minute.hours(), Err(DCF77Error::FrameIncomplete));
+        assert_ne!(minute, DCF77Time::new(bits));
+        assert_eq!(DCF77Time::with_received_bits(bits, 80), DCF77Time::new(bits));
+    }
+
+    #[test]
+    fn continuous_decoding_of_consecutive_minutes() {
+        let times = [
+            time(2024, 12, 31, 23, 58, false),
+            time(2024, 12, 31, 23, 59, false),
+            time(2025, 1, 1, 0, 0, false),
+        ];
+        let frames: Vec<u64> = times.iter().map(frame).collect();
+        let mut decoder = SimpleDCF77Decoder::new();
+
+        assert_eq!(
+            decode_all(&mut decoder, &signal(&frames)),
+            [Ok(times[0]), Ok(times[1]), Ok(times[2])]
+        );
+    }
+
+    #[test]
+    fn continuous_decoding_recovers_from_faults_and_dropouts() {
+        let frames: Vec<u64> = (30..35).map(|m| frame(&time(2024, 3, 15, 13, m, false))).collect();
+        let mut samples = Vec::new();
+        second(&mut samples, 0, 10);
+        minute(&mut samples, frames[0], 10);
+        // A 20ms glitch instead of the 0 bit of second 5 is a faulty bit within the weather bits
+        bits(&mut samples, frames[1], 0, 5, 10);
+        second(&mut samples, 20, 10);
+        bits(&mut samples, frames[1], 6, 59, 10);
+        second(&mut samples, 0, 10);
+        // A dropout of 10 seconds in the middle of a minute
+        bits(&mut samples, frames[2], 0, 20, 10);
+        for _ in 20..30 {
+            second(&mut samples, 0, 10);
+        }
+        bits(&mut samples, frames[2], 30, 59, 10);
+        second(&mut samples, 0, 10);
+        minute(&mut samples, frames[3], 10);
+        minute(&mut samples, frames[4], 10);
+
+        let mut decoder = SimpleDCF77Decoder::new();
+        let mut minutes = Vec::new();
+        let mut faults = Vec::new();
+        let mut short_cycles = 0;
+        for &sample in &samples {
+            let faulty = decoder.bit_faulty();
+            decoder.read_bit(sample);
+            if decoder.bit_faulty() && !faulty {
+                faults.extend(decoder.last_fault());
+            }
+            if decoder.end_of_cycle() {
+                minutes.push(decoder.received_frame().decode().map(|time| time.minute));
+            } else if decoder.cycle_ended() {
+                short_cycles += 1;
+            }
+        }
+
+        // After the dropout the first received bit is taken as start bit of a new minute, which
+        // has to be 0
+        assert_eq!(faults, [FaultKind::AmbiguousBit, FaultKind::StartBitSet]);
+        assert!(short_cycles > 0);
+        assert_eq!(minutes, [Ok(30), Ok(31), Ok(33), Ok(34)]);
     }
 }
@@ -0,0 +1,82 @@
+//! Synthetic DCF77 signals shared by the tests
+
+use std::vec::Vec;
+
+use {DCF77Error, DCF77Time, DateTime, SimpleDCF77Decoder};
+
+/// Return the date/time with the weekday calculated from the date
+pub fn time(year: u16, month: u8, day: u8, hour: u8, minute: u8, cest: bool) -> DateTime {
+    DateTime {
+        year,
+        month,
+        day,
+        weekday: 0,
+        hour,
+        minute,
+        cest,
+    }
+    .add_minutes(0)
+}
+
+/// Return the frame transmitted for the given date/time
+pub fn frame(time: &DateTime) -> u64 {
+    DCF77Time::encode(time).bits()
+}
+
+/// Append the samples of one second starting with a pulse of `pulse_ms`, 0 meaning no pulse
+pub fn second(samples: &mut Vec<bool>, pulse_ms: u16, period_ms: u16) {
+    for sample in 0..1000 / period_ms {
+        samples.push(sample * period_ms < pulse_ms);
+    }
+}
+
+/// Append the samples of the seconds `from` up to (excluding) `to` of the frame, a 0 bit being
+/// sent as 100ms and a 1 bit as 200ms pulse
+pub fn bits(samples: &mut Vec<bool>, frame: u64, from: usize, to: usize, period_ms: u16) {
+    for bit in from..to {
+        let pulse_ms = if (frame >> bit) & 1 != 0 { 200 } else { 100 };
+        second(samples, pulse_ms, period_ms);
+    }
+}
+
+/// Append the samples of a complete minute transmitting the frame, ending with the minute marker
+pub fn minute(samples: &mut Vec<bool>, frame: u64, period_ms: u16) {
+    bits(samples, frame, 0, 59, period_ms);
+    second(samples, 0, period_ms);
+}
+
+/// Return the samples of the frames transmitted back to back every 10ms, starting with one second
+/// without a pulse
+pub fn signal(frames: &[u64]) -> Vec<bool> {
+    let mut samples = Vec::new();
+    second(&mut samples, 0, 10);
+    for &frame in frames {
+        minute(&mut samples, frame, 10);
+    }
+
+    samples
+}
+
+/// Return the samples of a signal sampled every 10ms as taken by a sample clock running too fast
+/// or too slow, taking `per_second` instead of 100 samples per second
+pub fn resample(samples: &[bool], per_second: usize) -> Vec<bool> {
+    (0..samples.len() * per_second / 100)
+        .map(|sample| samples[sample * 100 / per_second])
+        .collect()
+}
+
+/// Feed the samples into the decoder and return the decoded frame of every complete minute
+pub fn decode_all(
+    decoder: &mut SimpleDCF77Decoder,
+    samples: &[bool],
+) -> Vec<Result<DateTime, DCF77Error>> {
+    let mut decoded = Vec::new();
+    for &sample in samples {
+        decoder.read_bit(sample);
+        if decoder.end_of_cycle() {
+            decoded.push(decoder.received_frame().decode());
+        }
+    }
+
+    decoded
+}
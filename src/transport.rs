@@ -0,0 +1,109 @@
+use DCF77Time;
+
+/// The marker starting every transport packet
+const SYNC: [u8; 2] = [0xdc, 0xf7];
+
+/// The reasons why decoding a transport packet can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransportError {
+    /// The packet doesn't start with the sync marker
+    SyncMismatch,
+    /// The checksum doesn't match the frame bytes
+    CrcMismatch,
+}
+
+/// Calculate the CRC-16/CCITT-FALSE checksum over the given bytes
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// A transport packet consists of the sync marker `0xdc 0xf7`, the 8 bytes of the frame in little
+/// endian order and a big endian CRC-16/CCITT-FALSE over the frame bytes, suitable for forwarding
+/// frames over a lossy serial link.
+///
+/// The number of received bits isn't part of the packet, so a decoded packet always holds a
+/// complete 59 bit frame. Only send frames of completed cycles, e.g. from
+/// `SimpleDCF77Decoder::take_completed_frame`, not partially received ones
+impl DCF77Time {
+    /// Encode the frame into a transport packet, see above for why it should be complete
+    pub fn encode_transport(&self) -> [u8; 12] {
+        let mut packet = [0; 12];
+        packet[..2].copy_from_slice(&SYNC);
+        packet[2..10].copy_from_slice(&self.bits().to_le_bytes());
+        let crc = crc16(&packet[2..10]);
+        packet[10..].copy_from_slice(&crc.to_be_bytes());
+
+        packet
+    }
+
+    /// Decode a transport packet after verifying the sync marker and checksum
+    pub fn decode_transport(packet: &[u8; 12]) -> Result<DCF77Time, TransportError> {
+        if packet[..2] != SYNC {
+            return Err(TransportError::SyncMismatch);
+        }
+
+        if crc16(&packet[2..10]).to_be_bytes() != packet[10..] {
+            return Err(TransportError::CrcMismatch);
+        }
+
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&packet[2..10]);
+
+        Ok(DCF77Time::new(u64::from_le_bytes(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testutil::*;
+    use {DCF77Time, TransportError};
+
+    #[test]
+    fn transport_round_trip() {
+        let sent = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false)));
+        let packet = sent.encode_transport();
+
+        assert_eq!(packet[..2], [0xdc, 0xf7]);
+        assert_eq!(DCF77Time::decode_transport(&packet), Ok(sent));
+        assert_eq!(
+            DCF77Time::decode_transport(&packet).map(|received| received.decode()),
+            Ok(Ok(time(2024, 3, 15, 13, 37, false)))
+        );
+    }
+
+    #[test]
+    fn corrupted_packets_are_rejected() {
+        let packet = DCF77Time::new(frame(&time(2024, 3, 15, 13, 37, false))).encode_transport();
+
+        for byte in 2..10 {
+            let mut corrupt = packet;
+            corrupt[byte] ^= 0x10;
+            assert_eq!(DCF77Time::decode_transport(&corrupt), Err(TransportError::CrcMismatch));
+        }
+
+        for byte in 10..12 {
+            let mut corrupt = packet;
+            corrupt[byte] ^= 0x01;
+            assert_eq!(DCF77Time::decode_transport(&corrupt), Err(TransportError::CrcMismatch));
+        }
+
+        for byte in 0..2 {
+            let mut corrupt = packet;
+            corrupt[byte] ^= 0x80;
+            assert_eq!(DCF77Time::decode_transport(&corrupt), Err(TransportError::SyncMismatch));
+        }
+    }
+}